@@ -0,0 +1,164 @@
+// Compares the generated shift-and-mask accessors against the bit-at-a-time
+// loop that #[bitfield] used to expand to, on fields that straddle byte
+// boundaries (the EdgeCaseBytes layout from tests/12-accessors-edge.rs).
+//
+//     cargo bench --bench accessors
+
+use bitfield::*;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+#[bitfield]
+pub struct EdgeCaseBytes {
+    a: B9,
+    b: B6,
+    c: B13,
+    d: B4,
+}
+
+/// The same layout as `EdgeCaseBytes`, with accessors written the way the
+/// macro used to generate them: one `Specifier` call per bit.
+struct BitLoop {
+    data: [u8; 4],
+}
+
+impl BitLoop {
+    fn get(&self, pad: usize, bits: usize) -> u64 {
+        let mut val = 0;
+
+        for cur in 0..bits {
+            let i = cur + pad;
+            let bit = bits - (cur + 1);
+
+            if self.data[i / 8] & (1 << (i % 8)) != 0 {
+                val |= 1 << bit;
+            }
+        }
+
+        val
+    }
+
+    fn set(&mut self, pad: usize, bits: usize, value: u64) {
+        for cur in 0..bits {
+            let i = pad + cur;
+            let mag = bits - (cur + 1);
+
+            if value & (1 << mag) != 0 {
+                self.data[i / 8] |= 1 << (i % 8);
+            } else {
+                self.data[i / 8] &= !(1 << (i % 8));
+            }
+        }
+    }
+}
+
+fn inputs() -> Vec<(u16, u8, u16, u8)> {
+    let mut seed = 0x2545_f491_4f6c_dd1d_u64;
+
+    (0..1024)
+        .map(|_| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+
+            let a = (seed & 0x1ff) as u16;
+            let b = (seed >> 9 & 0x3f) as u8;
+            let c = (seed >> 15 & 0x1fff) as u16;
+            let d = (seed >> 28 & 0xf) as u8;
+
+            (a, b, c, d)
+        })
+        .collect()
+}
+
+fn getters(c: &mut Criterion) {
+    let inputs = inputs();
+    let mut group = c.benchmark_group("EdgeCaseBytes getters");
+
+    let packed = inputs
+        .iter()
+        .map(|&(a, b, c, d)| {
+            let mut bitfield = EdgeCaseBytes::new();
+            bitfield.set_a(a);
+            bitfield.set_b(b);
+            bitfield.set_c(c);
+            bitfield.set_d(d);
+            bitfield
+        })
+        .collect::<Vec<_>>();
+
+    group.bench_function("shift-and-mask", |bench| {
+        bench.iter(|| {
+            black_box(&packed).iter().fold(0u64, |sum, bitfield| {
+                sum + bitfield.get_a() as u64
+                    + bitfield.get_b() as u64
+                    + bitfield.get_c() as u64
+                    + bitfield.get_d() as u64
+            })
+        });
+    });
+
+    let packed = inputs
+        .iter()
+        .map(|&(a, b, c, d)| {
+            let mut bitfield = BitLoop { data: [0; 4] };
+            bitfield.set(0, 9, a as u64);
+            bitfield.set(9, 6, b as u64);
+            bitfield.set(15, 13, c as u64);
+            bitfield.set(28, 4, d as u64);
+            bitfield
+        })
+        .collect::<Vec<_>>();
+
+    group.bench_function("bit-loop", |bench| {
+        bench.iter(|| {
+            black_box(&packed).iter().fold(0u64, |sum, bitfield| {
+                sum + bitfield.get(0, 9)
+                    + bitfield.get(9, 6)
+                    + bitfield.get(15, 13)
+                    + bitfield.get(28, 4)
+            })
+        });
+    });
+
+    group.finish();
+}
+
+fn setters(c: &mut Criterion) {
+    let inputs = inputs();
+    let mut group = c.benchmark_group("EdgeCaseBytes setters");
+
+    group.bench_function("shift-and-mask", |bench| {
+        let mut packed = (0..inputs.len())
+            .map(|_| EdgeCaseBytes::new())
+            .collect::<Vec<_>>();
+
+        bench.iter(|| {
+            for (bitfield, &(a, b, c, d)) in packed.iter_mut().zip(black_box(&inputs)) {
+                bitfield.set_a(a);
+                bitfield.set_b(b);
+                bitfield.set_c(c);
+                bitfield.set_d(d);
+            }
+        });
+    });
+
+    group.bench_function("bit-loop", |bench| {
+        let mut packed = (0..inputs.len())
+            .map(|_| BitLoop { data: [0; 4] })
+            .collect::<Vec<_>>();
+
+        bench.iter(|| {
+            for (bitfield, &(a, b, c, d)) in packed.iter_mut().zip(black_box(&inputs)) {
+                bitfield.set(0, 9, a as u64);
+                bitfield.set(9, 6, b as u64);
+                bitfield.set(15, 13, c as u64);
+                bitfield.set(28, 4, d as u64);
+            }
+        });
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, getters, setters);
+criterion_main!(benches);
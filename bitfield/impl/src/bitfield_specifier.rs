@@ -19,71 +19,101 @@ pub fn derive(input: DeriveInput) -> syn::Result<TokenStream> {
         syn::Data::Enum(e) => e,
     };
 
-    let bits = {
-        let len = e.variants.len();
-
-        if len.is_power_of_two() {
-            (usize::BITS - len.leading_zeros()) as usize - 1
-        } else {
-            return Err(Error::new(
-                Span::call_site(),
-                "BitfieldSpecifier expected a number of variants which is a power of 2",
-            ));
+    let declared = declared_bits(&input.attrs)?;
+
+    let bits = match declared {
+        Some(bits) => bits,
+        None => {
+            let len = e.variants.len();
+
+            if len.is_power_of_two() {
+                (usize::BITS - len.leading_zeros()) as usize - 1
+            } else {
+                return Err(Error::new(
+                    Span::call_site(),
+                    "BitfieldSpecifier expected a number of variants which is a power of 2",
+                ));
+            }
         }
     };
 
-    let subty = match bits {
-        1..=8 => quote! { <B8 as ::bitfield::Specifier> },
-        9..=16 => quote! { <B8 as ::bitfield::Specifier> },
-        17..=32 => quote! { <B8 as ::bitfield::Specifier> },
-        33..=64 => quote! { <B8 as ::bitfield::Specifier> },
-
-        _ => panic!("wtf"),
-    };
+    // Unless the variants cover every pattern of `bits` bits, getters have to
+    // be able to report patterns that don't name a variant.
+    let exhaustive = bits < usize::BITS as usize && e.variants.len() == 1 << bits;
 
     let ty_name = &input.ident;
 
-    let match_expr = {
-        let names = e.variants.iter().map(|v| {
+    // Discriminants are compared as u64 so that enums of any width share the
+    // same code. Each one gets a named constant so that it can be used as a
+    // match pattern, whatever expression the discriminant was written with.
+    let constants = (0..e.variants.len())
+        .map(|i| format_ident!("DISCRIMINANT_{}", i))
+        .collect::<Vec<_>>();
+
+    let from_bits = {
+        let names = e.variants.iter().zip(&constants).map(|(v, constant)| {
             let ident = &v.ident;
-            let constant = format_ident!("{}", ident.to_string().to_uppercase());
 
             quote! {
-                const #constant: #subty::Bucket = #ty_name::#ident as #subty::Bucket;
+                const #constant: u64 = #ty_name::#ident as u64;
             }
         });
 
-        let arms = e.variants.iter().map(|v| {
+        let arms = e.variants.iter().zip(&constants).map(|(v, constant)| {
             let ident = &v.ident;
-            let constant = format_ident!("{}", ident.to_string().to_uppercase());
 
-            quote! {
-                #constant => Self::#ident,
+            if exhaustive {
+                quote! { #constant => Self::#ident, }
+            } else {
+                quote! { #constant => ::std::result::Result::Ok(Self::#ident), }
             }
         });
 
+        let fallback = if exhaustive {
+            // The variant count is 2^BITS and every discriminant is checked
+            // to be below it, so every pattern of BITS bits names exactly one
+            // variant.
+            quote! { ::std::unreachable!() }
+        } else {
+            quote! { ::std::result::Result::Err(::bitfield::InvalidBitPattern::new(bits)) }
+        };
+
         quote! {
-            {
-                #(#names)*
+            #(#names)*
 
-                match val {
-                    #(#arms)*
-                    _ => todo!("unspecified value"),
-                }
+            match bits {
+                #(#arms)*
+                _ => #fallback,
             }
         }
     };
 
+    // Exhaustive enums have a variant for every pattern of `bits` bits, so
+    // any higher bits passed to `from_bits` are ignored rather than rejected.
+    let mask = exhaustive.then(|| {
+        let mask = (1u64 << bits) - 1;
+        quote! { let bits = bits & #mask; }
+    });
+
+    let output = if exhaustive {
+        quote! { Self }
+    } else {
+        quote! { ::std::result::Result<Self, ::bitfield::InvalidBitPattern> }
+    };
+
     let mut checks = TokenStream::default();
 
-    for var in &e.variants {
-        let var_name = &var.ident;
-        let msg = format!("{var_name} is out of range for type {ty_name}");
-        let cap = e.variants.len();
+    if bits < 64 {
+        let cap = 1u64 << bits;
 
-        checks.extend(quote! {
-            const _: () = assert!(#cap as #subty::Bucket > #ty_name::#var_name as #subty::Bucket  , #msg);
-        });
+        for var in &e.variants {
+            let var_name = &var.ident;
+            let msg = format!("{var_name} is out of range for type {ty_name}");
+
+            checks.extend(quote! {
+                const _: () = assert!(#cap > #ty_name::#var_name as u64, #msg);
+            });
+        }
     }
 
     Ok(quote! {
@@ -97,29 +127,47 @@ pub fn derive(input: DeriveInput) -> syn::Result<TokenStream> {
 
         impl ::std::marker::Copy for #ty_name { }
 
-        impl Specifier for #ty_name {
+        impl ::bitfield::Specifier for #ty_name {
             type Bucket = Self;
+            type Output = #output;
             const BITS: usize = #bits;
 
-            fn is_set(bucket: &Self, bit: usize) -> bool {
-                #subty::is_set(&(*bucket as #subty::Bucket), bit)
+            fn into_bits(bucket: Self) -> u128 {
+                // Through u64, so that negative discriminants of 64-bit enums
+                // are not sign-extended.
+                bucket as u64 as u128
             }
 
-            fn set(bucket: &mut Self, bit: usize) {
-                let mut val = #subty::empty();
-                val = *bucket as #subty::Bucket;
-                #subty::set(&mut val, bit);
-                *bucket = #match_expr;
+            fn from_bits(bits: u128) -> #output {
+                // Enums are at most 64 bits wide.
+                let bits = bits as u64;
+                #mask
 
-                //#subty::set(&mut (*bucket as #subty::Bucket), bit);
+                #from_bits
             }
+        }
+    })
+}
 
-            fn empty() -> Self {
-                let val = 0;
+/// Reads the width from a `#[bits = N]` attribute on the enum, if any.
+fn declared_bits(attrs: &[syn::Attribute]) -> syn::Result<Option<usize>> {
+    let Some(attr) = attrs.iter().find(|a| a.path().is_ident("bits")) else {
+        return Ok(None);
+    };
 
-                #match_expr
+    let syn::Meta::NameValue(syn::MetaNameValue {
+        value: syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(lit),
+            ..
+        }),
+        ..
+    }) = &attr.meta
+    else {
+        return Err(Error::new_spanned(attr, "expected `#[bits = N]`"));
+    };
 
-            }
-        }
-    })
+    match lit.base10_parse::<usize>()? {
+        bits @ 1..=64 => Ok(Some(bits)),
+        _ => Err(Error::new_spanned(lit, "expected a width between 1 and 64")),
+    }
 }
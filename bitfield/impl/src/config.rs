@@ -0,0 +1,160 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{meta::ParseNestedMeta, Ident, LitBool, LitStr};
+
+/// Options given to the attribute itself, e.g.
+/// `#[bitfield(bits = "msb0", endian = "big")]`.
+pub struct Config {
+    msb0: bool,
+    little_endian: bool,
+    /// Whether the fields have to fill every byte of the storage. Cleared by
+    /// `filled = false`, which rounds the storage up to whole bytes instead.
+    pub filled: bool,
+    /// The integer the struct is stored as, from `repr = u32`, instead of a
+    /// byte array.
+    pub repr: Option<Ident>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            msb0: false,
+            little_endian: false,
+            filled: true,
+            repr: None,
+        }
+    }
+}
+
+impl Config {
+    pub fn parse(&mut self, meta: ParseNestedMeta) -> syn::Result<()> {
+        if meta.path.is_ident("bits") {
+            let value: LitStr = meta.value()?.parse()?;
+            self.msb0 = match value.value().as_str() {
+                "lsb0" => false,
+                "msb0" => true,
+                _ => return Err(syn::Error::new(value.span(), "expected \"lsb0\" or \"msb0\"")),
+            };
+
+            Ok(())
+        } else if meta.path.is_ident("endian") {
+            let value: LitStr = meta.value()?.parse()?;
+            self.little_endian = match value.value().as_str() {
+                "big" => false,
+                "little" => true,
+                _ => return Err(syn::Error::new(value.span(), "expected \"big\" or \"little\"")),
+            };
+
+            Ok(())
+        } else if meta.path.is_ident("filled") {
+            let value: LitBool = meta.value()?.parse()?;
+            self.filled = value.value;
+
+            Ok(())
+        } else if meta.path.is_ident("repr") {
+            let repr: Ident = meta.value()?.parse()?;
+            if !["u8", "u16", "u32", "u64", "u128"].iter().any(|ty| repr == ty) {
+                return Err(syn::Error::new(
+                    repr.span(),
+                    "expected one of u8, u16, u32, u64 or u128",
+                ));
+            }
+            self.repr = Some(repr);
+
+            Ok(())
+        } else {
+            Err(meta.error("unsupported bitfield option"))
+        }
+    }
+
+    /// Converts the storage `data` into its bytes. Integers are split so that
+    /// bit 0 is their least significant bit with `lsb0` and their most
+    /// significant one with `msb0`.
+    pub fn bytes_of(&self, data: TokenStream) -> TokenStream {
+        match &self.repr {
+            Some(repr) => {
+                let method = format_ident!("to_{}_bytes", self.integer_endian());
+                quote! { ::std::primitive::#repr::#method(#data) }
+            }
+            None => data,
+        }
+    }
+
+    /// Converts the byte array `bytes` into the storage.
+    pub fn storage_of(&self, bytes: TokenStream) -> TokenStream {
+        match &self.repr {
+            Some(repr) => {
+                let method = format_ident!("from_{}_bytes", self.integer_endian());
+                quote! { ::std::primitive::#repr::#method(#bytes) }
+            }
+            None => bytes,
+        }
+    }
+
+    fn integer_endian(&self) -> &'static str {
+        if self.msb0 {
+            "be"
+        } else {
+            "le"
+        }
+    }
+
+    /// Reads the `width`-bit field at `offset` out of the storage `data`.
+    pub fn read(&self, data: TokenStream, offset: TokenStream, width: TokenStream) -> TokenStream {
+        let layout = self.layout();
+
+        match &self.repr {
+            Some(_) => {
+                let bytes = self.bytes_of(data);
+                quote! { ::bitfield::access::read(&#bytes, #offset, #width, #layout) }
+            }
+            None => quote! { ::bitfield::access::read(&#data, #offset, #width, #layout) },
+        }
+    }
+
+    /// Writes `value` to the `width`-bit field at `offset` of the storage
+    /// `data`, which has to be a place expression.
+    pub fn write(
+        &self,
+        data: TokenStream,
+        offset: TokenStream,
+        width: TokenStream,
+        value: TokenStream,
+    ) -> TokenStream {
+        let layout = self.layout();
+
+        match &self.repr {
+            Some(_) => {
+                let to_bytes = self.bytes_of(data.clone());
+                let from_bytes = self.storage_of(quote! { bytes });
+                quote! {
+                    let mut bytes = #to_bytes;
+                    ::bitfield::access::write(&mut bytes, #offset, #width, #layout, #value);
+                    #data = #from_bytes;
+                }
+            }
+            None => quote! {
+                ::bitfield::access::write(&mut #data, #offset, #width, #layout, #value);
+            },
+        }
+    }
+
+    /// The `::bitfield::access::Layout` handed to every accessor.
+    pub fn layout(&self) -> TokenStream {
+        let bits = if self.msb0 {
+            quote! { ::bitfield::access::BitOrder::Msb0 }
+        } else {
+            quote! { ::bitfield::access::BitOrder::Lsb0 }
+        };
+
+        let endian = if self.little_endian {
+            quote! { ::bitfield::access::Endian::Little }
+        } else {
+            quote! { ::bitfield::access::Endian::Big }
+        };
+
+        quote! {
+            ::bitfield::access::Layout { bits: #bits, endian: #endian }
+        }
+    }
+}
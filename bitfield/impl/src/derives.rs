@@ -0,0 +1,120 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{punctuated::Punctuated, Attribute, Ident, Path, Token};
+
+use crate::{
+    config::Config,
+    fields::{self, Field},
+};
+
+/// The traits named in the `#[derive(...)]` attributes of the input.
+///
+/// Other traits (`Clone`, `Copy`, `PartialEq`, `Eq`, `Hash`, ...) are forwarded
+/// to the generated struct and so work on the packed bytes. Those hold nothing
+/// but the fields: the unused bits of `filled = false` structs are cleared by
+/// `from_bytes`. `#[skip]` padding is a field as far as storage goes, so it is
+/// compared and hashed even though `Debug` leaves it out, as reserved bits
+/// read from hardware are meant to be preserved.
+///
+/// `Debug` and `Default` get impls in terms of the fields.
+#[derive(Default)]
+pub struct Derives {
+    debug: bool,
+    default: bool,
+    forwarded: Vec<Path>,
+}
+
+impl Derives {
+    /// Splits the `#[derive(...)]` attributes off `attrs`, returning the
+    /// remaining attributes.
+    pub fn extract(&mut self, attrs: &[Attribute]) -> syn::Result<Vec<Attribute>> {
+        let mut kept = Vec::new();
+
+        for attr in attrs {
+            if !attr.path().is_ident("derive") {
+                kept.push(attr.clone());
+                continue;
+            }
+
+            let paths = attr.parse_args_with(Punctuated::<Path, Token![,]>::parse_terminated)?;
+            for path in paths {
+                let last = path.segments.last().map(|s| &s.ident);
+
+                if last.is_some_and(|i| i == "Debug") {
+                    self.debug = true;
+                } else if last.is_some_and(|i| i == "Default") {
+                    self.default = true;
+                } else {
+                    self.forwarded.push(path);
+                }
+            }
+        }
+
+        Ok(kept)
+    }
+
+    /// The `#[derive(...)]` attribute to put on the generated struct.
+    pub fn attr(&self) -> TokenStream {
+        if self.forwarded.is_empty() {
+            quote! {}
+        } else {
+            let forwarded = &self.forwarded;
+            quote! { #[derive(#(#forwarded),*)] }
+        }
+    }
+
+    pub fn impls(&self, name: &Ident, fields: &[Field], config: &Config) -> TokenStream {
+        let mut tokens = TokenStream::default();
+
+        if self.debug {
+            // Fields are read directly rather than through their getters,
+            // which may be skipped or less visible than the impl.
+            let shown = fields.iter().enumerate().filter(|(_, f)| !f.padding);
+            let labels = shown.clone().map(|(_, f)| f.name.to_string());
+            let values = shown.map(|(cur, field)| {
+                let offset = fields::offset(fields, cur);
+                let ty = &field.ty;
+                let tr = quote! { <#ty as ::bitfield::Specifier> };
+
+                let read = |offset| {
+                    let bits = config.read(quote! { self.data }, offset, quote! { #tr::BITS });
+                    quote! { #tr::from_bits(#bits) }
+                };
+
+                match &field.len {
+                    Some(len) => {
+                        let element = read(quote! { #offset + index * #tr::BITS });
+                        quote! {{
+                            let elements: [#tr::Output; #len] = ::std::array::from_fn(|index| #element);
+                            elements
+                        }}
+                    }
+                    None => read(offset),
+                }
+            });
+            let label = name.to_string();
+
+            tokens.extend(quote! {
+                impl ::std::fmt::Debug for #name {
+                    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                        f.debug_struct(#label)
+                            #(.field(#labels, &#values))*
+                            .finish()
+                    }
+                }
+            });
+        }
+
+        if self.default {
+            tokens.extend(quote! {
+                impl ::std::default::Default for #name {
+                    fn default() -> Self {
+                        Self::new()
+                    }
+                }
+            });
+        }
+
+        tokens
+    }
+}
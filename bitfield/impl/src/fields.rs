@@ -1,20 +1,127 @@
-use syn::{visit::Visit, Attribute, Ident, Meta, Type};
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, quote_spanned};
+use syn::{parse::ParseStream, spanned::Spanned, visit::Visit, Attribute, Ident, Meta, Token, Type, Visibility};
+
+/// Specifiers from the runtime crate whose conversions are plain casts, so
+/// that their accessors can be `const fn`s. They are recognized by name, as
+/// trait methods cannot be called in a `const fn`.
+///
+/// Only `bool` and paths into the runtime crate count: `B8`, `bitfield::B8`
+/// and `::bitfield::B8`. The bare names are thus reserved: a user type
+/// called `B8` must be named through its module, e.g. `regs::B8`, and using
+/// it under the bare name is rejected by `builtin_check`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Builtin {
+    /// `B1` to `B128`.
+    Unsigned,
+    /// `I2` to `I64`.
+    Signed,
+    Bool,
+}
+
+impl Builtin {
+    fn of(ty: &Type) -> Option<Self> {
+        let Type::Path(syn::TypePath { qself: None, path }) = ty else {
+            return None;
+        };
+        let last = path.segments.last()?;
+        let mut leading = path.segments.iter().rev().skip(1);
+        if !last.arguments.is_none() || leading.any(|s| s.ident != "bitfield") {
+            return None;
+        }
+
+        let name = last.ident.to_string();
+        if name == "bool" && path.segments.len() == 1 {
+            return Some(Builtin::Bool);
+        }
+
+        let width = |prefix| name.strip_prefix(prefix)?.parse::<usize>().ok();
+        match (width('B'), width('I')) {
+            (Some(1..=128), _) => Some(Builtin::Unsigned),
+            (_, Some(2..=64)) => Some(Builtin::Signed),
+            _ => None,
+        }
+    }
+}
 
 pub struct Field {
     pub name: Ident,
+    /// The specifier, or for array fields the specifier of each element.
     pub ty: Type,
+    /// The length of array fields such as `prio: [B4; 8]`.
+    pub len: Option<syn::Expr>,
     pub constraint: Option<syn::Expr>,
+    pub builtin: Option<Builtin>,
+    /// Visibility of the accessors: the field's own, unless overridden with
+    /// `#[vis = "..."]`.
+    pub vis: Visibility,
+    /// Cleared by `#[skip(getters)]`.
+    pub getters: bool,
+    /// Cleared by `#[skip(setters)]`.
+    pub setters: bool,
+    /// Type taken and returned by the accessors instead of the specifier's
+    /// own, set with `#[bitfield(repr = T)]`.
+    pub repr: Option<Type>,
+    /// Set by `#[skip]` and `#[reserved(...)]`: the field takes up space but
+    /// has no accessors and is left out of `Debug`.
+    pub padding: bool,
+    /// The bits `new` stores in the field, from `#[reserved(value = ...)]`.
+    pub reserved: Option<syn::Expr>,
+    /// Where the field starts, from `#[at(12)]` or `#[bits(12..=15)]`.
+    /// Fields without one follow the previous field.
+    pub at: Option<syn::Expr>,
+    /// The width given by `#[bits(12..=15)]`, checked against the specifier,
+    /// and the span of the range.
+    pub range_width: Option<(TokenStream, Span)>,
+}
+
+impl Field {
+    /// The width of the field in bits, i.e. that of its specifier times the
+    /// length for array fields.
+    pub fn bits(&self) -> TokenStream {
+        let ty = &self.ty;
+
+        match &self.len {
+            Some(len) => quote! { (<#ty as ::bitfield::Specifier>::BITS * (#len)) },
+            None => quote! { <#ty as ::bitfield::Specifier>::BITS },
+        }
+    }
+
+    fn make_padding(&mut self) {
+        self.padding = true;
+        self.getters = false;
+        self.setters = false;
+    }
 }
 
 #[derive(Default)]
 pub struct Fields {
     fields: Vec<Field>,
+    error: Option<syn::Error>,
+}
+
+impl Fields {
+    /// The first error found in the field attributes, with any further ones
+    /// combined into it.
+    pub fn error(&self) -> Option<&syn::Error> {
+        self.error.as_ref()
+    }
+
+    fn push_error(&mut self, e: syn::Error) {
+        match &mut self.error {
+            Some(error) => error.combine(e),
+            None => self.error = Some(e),
+        }
+    }
 }
 
 impl<'ast> Visit<'ast> for Fields {
     fn visit_field(&mut self, i: &'ast syn::Field) {
         let name = i.ident.clone().unwrap();
-        let ty = i.ty.clone();
+        let (ty, len) = match &i.ty {
+            Type::Array(array) => ((*array.elem).clone(), Some(array.len.clone())),
+            ty => (ty.clone(), None),
+        };
 
         let constraint = i
             .attrs
@@ -32,12 +139,203 @@ impl<'ast> Visit<'ast> for Fields {
                 }
             });
 
-        self.fields.push(Field {
+        let mut field = Field {
             name,
+            builtin: Builtin::of(&ty),
             ty,
+            len,
             constraint,
-        })
+            vis: i.vis.clone(),
+            getters: true,
+            setters: true,
+            repr: None,
+            padding: false,
+            reserved: None,
+            at: None,
+            range_width: None,
+        };
+
+        for attr in &i.attrs {
+            let result = if attr.path().is_ident("vis") {
+                parse_vis(attr).map(|vis| field.vis = vis)
+            } else if attr.path().is_ident("skip") {
+                parse_skip(attr, &mut field)
+            } else if attr.path().is_ident("at") {
+                attr.parse_args().map(|at| field.at = Some(at))
+            } else if attr.path().is_ident("bits") && matches!(attr.meta, Meta::List(_)) {
+                parse_range(attr, &mut field)
+            } else if attr.path().is_ident("reserved") {
+                parse_reserved(attr, &mut field)
+            } else if attr.path().is_ident("bitfield") {
+                parse_options(attr, &mut field)
+            } else {
+                Ok(())
+            };
+
+            if let Err(e) = result {
+                self.push_error(e);
+            }
+        }
+
+        self.fields.push(field)
+    }
+}
+
+/// Parses `#[vis = "pub(crate)"]`.
+fn parse_vis(attr: &Attribute) -> syn::Result<Visibility> {
+    let Meta::NameValue(syn::MetaNameValue {
+        value: syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(lit),
+            ..
+        }),
+        ..
+    }) = &attr.meta
+    else {
+        return Err(syn::Error::new_spanned(attr, "expected `#[vis = \"...\"]`"));
+    };
+
+    lit.parse()
+        .map_err(|_| syn::Error::new(lit.span(), "expected a visibility"))
+}
+
+/// Parses `#[skip]`, `#[skip(getters)]`, `#[skip(setters)]` or
+/// `#[skip(getters, setters)]`.
+fn parse_skip(attr: &Attribute, field: &mut Field) -> syn::Result<()> {
+    let expected = "expected `#[skip]`, `#[skip(getters)]` or `#[skip(setters)]`";
+
+    let Meta::List(_) = &attr.meta else {
+        if let Meta::Path(_) = &attr.meta {
+            field.make_padding();
+
+            return Ok(());
+        }
+
+        return Err(syn::Error::new_spanned(attr, expected));
+    };
+
+    attr.parse_nested_meta(|meta| {
+        if meta.path.is_ident("getters") {
+            field.getters = false;
+        } else if meta.path.is_ident("setters") {
+            field.setters = false;
+        } else {
+            return Err(meta.error(expected));
+        }
+
+        Ok(())
+    })
+}
+
+/// Parses `#[bits(12..=15)]` or `#[bits(12..16)]`.
+fn parse_range(attr: &Attribute, field: &mut Field) -> syn::Result<()> {
+    attr.parse_args_with(|input: ParseStream| {
+        // Without syn's full expression parser the range cannot be parsed as
+        // an expression, so split it at the `..` by hand.
+        let mut start = TokenStream::default();
+        while !input.is_empty() && !input.peek(Token![..]) {
+            start.extend([input.parse::<proc_macro2::TokenTree>()?]);
+        }
+        let start: syn::Expr = syn::parse2(start)?;
+
+        let width = if input.peek(Token![..=]) {
+            input.parse::<Token![..=]>()?;
+            let end: syn::Expr = input.parse()?;
+            quote_spanned! {start.span()=> (#end) - (#start) + 1 }
+        } else if input.peek(Token![..]) {
+            input.parse::<Token![..]>()?;
+            let end: syn::Expr = input.parse()?;
+            quote_spanned! {start.span()=> (#end) - (#start) }
+        } else {
+            return Err(input.error("expected a range like `#[bits(12..=15)]`"));
+        };
+
+        field.range_width = Some((width, start.span()));
+        field.at = Some(start);
+
+        Ok(())
+    })
+}
+
+/// Parses `#[reserved(value = 0b101)]`.
+fn parse_reserved(attr: &Attribute, field: &mut Field) -> syn::Result<()> {
+    attr.parse_nested_meta(|meta| {
+        if meta.path.is_ident("value") {
+            field.reserved = Some(meta.value()?.parse()?);
+            field.make_padding();
+
+            Ok(())
+        } else {
+            Err(meta.error("expected `#[reserved(value = ...)]`"))
+        }
+    })
+}
+
+/// Parses `#[bitfield(repr = T)]`.
+fn parse_options(attr: &Attribute, field: &mut Field) -> syn::Result<()> {
+    attr.parse_nested_meta(|meta| {
+        if meta.path.is_ident("repr") {
+            field.repr = Some(meta.value()?.parse()?);
+
+            Ok(())
+        } else {
+            Err(meta.error("unsupported field option"))
+        }
+    })
+}
+
+/// The offset in bits of `fields[cur]`: its explicit position if it has one,
+/// and otherwise the end of the field before it.
+pub fn offset(fields: &[Field], cur: usize) -> TokenStream {
+    if let Some(at) = &fields[cur].at {
+        return quote! { (#at) };
+    }
+
+    // Sum the widths back to the nearest field with an explicit position.
+    let first = fields[..cur]
+        .iter()
+        .rposition(|f| f.at.is_some())
+        .unwrap_or(0);
+    let start = match &fields.get(first).and_then(|f| f.at.as_ref()) {
+        Some(at) => quote! { (#at) },
+        None => quote! { 0 },
+    };
+    let bits = fields[first..cur].iter().map(Field::bits);
+
+    quote! { #start #(+ #bits)* }
+}
+
+/// Checks that the fields recognized as `Builtin` name the runtime crate's
+/// types, rather than user types imported under the same name, whose
+/// conversions the `const` accessors would bypass.
+pub fn builtin_check(fields: &[Field]) -> TokenStream {
+    let mut tokens = TokenStream::default();
+
+    for field in fields {
+        let ty = &field.ty;
+        let expected = match field.builtin {
+            Some(Builtin::Bool) => quote! { ::std::primitive::bool },
+            Some(Builtin::Unsigned | Builtin::Signed) => {
+                let Type::Path(syn::TypePath { path, .. }) = ty else {
+                    continue;
+                };
+                let ident = &path.segments.last().unwrap().ident;
+
+                quote! { ::bitfield::#ident }
+            }
+            None => continue,
+        };
+
+        tokens.extend(quote_spanned! {ty.span()=>
+            const _: ::std::marker::PhantomData<#expected> = ::std::marker::PhantomData::<#ty>;
+        });
     }
+
+    tokens
+}
+
+/// Whether any field is placed explicitly rather than after the previous one.
+pub fn placed(fields: &[Field]) -> bool {
+    fields.iter().any(|f| f.at.is_some())
 }
 
 impl AsRef<[Field]> for Fields {
@@ -1,44 +1,78 @@
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
 
-use crate::fields::Field;
+use crate::{
+    config::Config,
+    fields::{self, Builtin, Field},
+};
 
-pub fn getters(fields: &[Field]) -> TokenStream {
+pub fn getters(fields: &[Field], config: &Config) -> TokenStream {
     let mut tokens = TokenStream::default();
     for cur in 0..fields.len() {
-        let prefix_types = fields[0..cur].iter().map(|f| &f.ty);
-
-        let pad = if cur == 0 {
-            quote! { 0 }
-        } else {
-            quote! { #(<#prefix_types as ::bitfield::Specifier>::BITS)+*}
-        };
-
         let field = &fields[cur];
+        if !field.getters {
+            continue;
+        }
+
+        let pad = fields::offset(fields, cur);
+        let vis = &field.vis;
 
         let method = format_ident!("get_{}", &field.name);
         let ty = &field.ty;
         let tr = quote! { <#ty as ::bitfield::Specifier> };
+        let width = quote! { #tr::BITS };
 
-        tokens.extend(quote! {
-            pub fn #method(&self) -> #tr::Bucket {
-                let pad = #pad;
-
-                let mut val = #tr::empty();
+        // Built-in specifiers convert with a cast or comparison, so their
+        // getters can be `const fn`s.
+        let (mut constness, mut from_bits) = match field.builtin {
+            Some(Builtin::Unsigned) => (quote! { const }, quote! { bits as #tr::Output }),
+            Some(Builtin::Signed) => (
+                quote! { const },
+                quote! { ::bitfield::access::sign_extend(bits, #tr::BITS) as #tr::Output },
+            ),
+            Some(Builtin::Bool) => (quote! { const }, quote! { bits != 0 }),
+            None => (quote! {}, quote! { #tr::from_bits(bits) }),
+        };
+        let mut output = quote! { #tr::Output };
 
-                for cur in 0..#tr::BITS {
-                    let i = cur + pad;
-                    let bit = #tr::BITS - (cur + 1);
+        if let Some(repr) = &field.repr {
+            constness = quote! {};
+            from_bits = quote! { <#repr as ::std::convert::From<#tr::Output>>::from(#from_bits) };
+            output = quote! { #repr };
+        }
 
-                    let b = i / 8;
-                    let s = i % 8;
+        let Some(len) = &field.len else {
+            let read = config.read(quote! { self.data }, quote! { OFFSET }, width);
+            tokens.extend(quote! {
+                #vis #constness fn #method(&self) -> #output {
+                    const OFFSET: usize = #pad;
 
-                    if self.data[b] & (1 << s) != 0 {
-                        #tr::set(&mut val, bit);
-                    }
+                    let bits = #read;
+                    #from_bits
                 }
+            });
+
+            continue;
+        };
+
+        // Array fields get indexed getters, and an iterator over all elements.
+        let iter = format_ident!("iter_{}", &field.name);
+        let msg = format!("index out of bounds for field `{}`", &field.name);
+        let read = config.read(quote! { self.data }, quote! { offset }, width);
+
+        tokens.extend(quote! {
+            #vis #constness fn #method(&self, index: usize) -> #output {
+                const OFFSET: usize = #pad;
+
+                assert!(index < #len, #msg);
+
+                let offset = OFFSET + index * #tr::BITS;
+                let bits = #read;
+                #from_bits
+            }
 
-                val
+            #vis fn #iter(&self) -> impl ::std::iter::Iterator<Item = #output> + '_ {
+                (0..#len).map(move |index| self.#method(index))
             }
         });
     }
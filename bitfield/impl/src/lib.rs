@@ -1,6 +1,8 @@
 use quote::quote;
 use syn::{parse_macro_input, visit::Visit, DeriveInput};
 
+mod config;
+mod derives;
 mod fields;
 mod getter;
 mod setter;
@@ -10,45 +12,167 @@ mod bitfield_specifier;
 
 #[proc_macro_attribute]
 pub fn bitfield(
-    _: proc_macro::TokenStream,
+    args: proc_macro::TokenStream,
     input: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
+    let mut config = config::Config::default();
+    let parser = syn::meta::parser(|meta| config.parse(meta));
+    parse_macro_input!(args with parser);
+
     let input = parse_macro_input!(input as DeriveInput);
 
     let mut fields = fields::Fields::default();
     fields.visit_derive_input(&input);
+    if let Some(e) = fields.error() {
+        return e.to_compile_error().into();
+    }
 
     let name = &input.ident;
+    let vis = &input.vis;
 
-    let size = size::byte_size(&fields);
-    let check = size::size_check(name, &fields);
+    let mut derives = derives::Derives::default();
+    let attrs = match derives.extract(&input.attrs) {
+        Ok(attrs) => attrs,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    let derive_attr = derives.attr();
+    let derive_impls = derives.impls(name, &fields, &config);
+
+    let bits = size::bit_size(&fields);
+    let size = size::byte_size(&fields, &config);
+    let check = size::size_check(name, &fields, &config);
+    let width_check = size::field_width_check(&fields);
+    let builtin_check = fields::builtin_check(&fields);
     let constraint_check = size::size_constraint(&fields);
+    let placement_check = size::placement_check(name, &fields);
+
+    let getters = getter::getters(&fields, &config);
+    let setters = setter::setters(&fields, &config);
+    let reserved = setter::reserved(&fields, &config);
+
+    // With `repr = uN` the struct is stored as that integer, converted to and
+    // from bytes around every access. Such a struct cannot lend out its bytes.
+    let storage = match &config.repr {
+        Some(repr) => quote! { ::std::primitive::#repr },
+        None => quote! { [u8; #size] },
+    };
+    let repr_attr = config.repr.as_ref().map(|_| quote! { #[repr(transparent)] });
+    let new_data = config.storage_of(quote! { data });
+    let into_bytes = config.bytes_of(quote! { self.data });
+    let as_bytes = config.repr.is_none().then(|| {
+        quote! {
+            pub const fn as_bytes(&self) -> &[u8; #size] {
+                &self.data
+            }
+        }
+    });
+    let layout = config.layout();
+
+    // The bits past the last field of a `filled = false` struct are unused.
+    // Bytes coming from outside have them cleared, so that derived
+    // comparisons and hashes only see the fields.
+    let clear_unused = (!config.filled).then(|| {
+        quote! {
+            let mut data = data;
+            ::bitfield::access::write(&mut data, #bits, #size * 8 - #bits, #layout, 0);
+        }
+    });
+    let from_repr = match &clear_unused {
+        Some(_) => {
+            let bytes = config.bytes_of(quote! { data });
+            quote! { Self::from_bytes(#bytes) }
+        }
+        None => quote! { Self { data } },
+    };
+
+    let conversions = config.repr.as_ref().map(|repr| {
+        quote! {
+            impl ::std::convert::From<::std::primitive::#repr> for #name {
+                fn from(data: ::std::primitive::#repr) -> Self {
+                    #from_repr
+                }
+            }
 
-    let getters = getter::getters(&fields);
-    let setters = setter::setters(&fields);
+            impl ::std::convert::From<#name> for ::std::primitive::#repr {
+                fn from(bitfield: #name) -> Self {
+                    bitfield.data
+                }
+            }
+        }
+    });
+    let into_bits = config.read(quote! { bucket.data }, quote! { 0 }, quote! { Self::BITS });
 
     quote! {
         #check
+        #width_check
+        #builtin_check
         #constraint_check
+        #placement_check
 
-        struct #name {
-            data: [u8; #size],
+        #(#attrs)*
+        #derive_attr
+        #repr_attr
+        #vis struct #name {
+            data: #storage,
         }
 
         impl #name {
-            fn new() -> Self {
-                let data = [0; #size];
-                Self { data }
+            #vis const fn new() -> Self {
+                #[allow(unused_mut)]
+                let mut data = [0; #size];
+                #reserved
+                Self { data: #new_data }
+            }
+
+            pub const fn from_bytes(data: [u8; #size]) -> Self {
+                #clear_unused
+                Self { data: #new_data }
             }
 
+            pub const fn into_bytes(self) -> [u8; #size] {
+                #into_bytes
+            }
+
+            #as_bytes
+
             #getters
             #setters
         }
+
+        impl ::std::convert::TryFrom<&[u8]> for #name {
+            type Error = ::std::array::TryFromSliceError;
+
+            fn try_from(bytes: &[u8]) -> ::std::result::Result<Self, Self::Error> {
+                <[u8; #size]>::try_from(bytes).map(Self::from_bytes)
+            }
+        }
+
+        #conversions
+
+        #derive_impls
+
+        // A bitfield nests into other bitfields as a single field spanning all
+        // of its bits, read and written with its own layout.
+        impl ::bitfield::Specifier for #name {
+            type Bucket = Self;
+            type Output = Self;
+            const BITS: usize = #bits;
+
+            fn into_bits(bucket: Self) -> u128 {
+                #into_bits
+            }
+
+            fn from_bits(bits: u128) -> Self {
+                let mut data = [0; #size];
+                ::bitfield::access::write(&mut data, 0, Self::BITS, #layout, bits);
+                Self { data: #new_data }
+            }
+        }
     }
     .into()
 }
 
-#[proc_macro_derive(BitfieldSpecifier)]
+#[proc_macro_derive(BitfieldSpecifier, attributes(bits))]
 pub fn bitfield_specifier(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
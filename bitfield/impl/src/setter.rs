@@ -1,39 +1,171 @@
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
 
-use crate::fields::Field;
+use crate::{
+    config::Config,
+    fields::{self, Builtin, Field},
+};
 
-pub fn setters(fields: &[Field]) -> TokenStream {
+pub fn setters(fields: &[Field], config: &Config) -> TokenStream {
     let mut tokens = TokenStream::default();
     for cur in 0..fields.len() {
-        let prefix_types = fields[0..cur].iter().map(|f| &f.ty);
-
-        let pad = if cur == 0 {
-            quote! { 0 }
-        } else {
-            quote! { #(<#prefix_types as ::bitfield::Specifier>::BITS)+*}
-        };
-
         let field = &fields[cur];
+        if !field.setters {
+            continue;
+        }
+
+        let pad = fields::offset(fields, cur);
+        let vis = &field.vis;
         let method = format_ident!("set_{}", &field.name);
+        let checked = format_ident!("set_{}_checked", &field.name);
+        let with = format_ident!("with_{}", &field.name);
         let ty = &field.ty;
         let tr = quote! { <#ty as ::bitfield::Specifier> };
+        let msg = format!("value out of bounds for field `{}`", &field.name);
 
-        tokens.extend(quote! {
-            pub fn #method(&mut self, value: #tr::Bucket) {
-                let pad = #pad;
-                for cur in 0..#tr::BITS {
-                    let i = pad + cur;
-                    let b = i / 8;
-                    let s = i % 8;
-                    let mag = #tr::BITS - (cur + 1);
-
-                    if #tr::is_set(&value, mag) {
-                        self.data[b] |= 1 << s;
-                    } else {
-                        self.data[b] &= !(1 << s);
+        // Built-in specifiers convert with a cast, so their setters can be
+        // `const fn`s and used to build constants.
+        let (mut constness, into_bits, in_bounds) = match field.builtin {
+            Some(Builtin::Signed) => (
+                quote! { const },
+                quote! { value as u128 },
+                quote! { ::bitfield::access::fits_signed(bits, #tr::BITS) },
+            ),
+            Some(Builtin::Unsigned | Builtin::Bool) => (
+                quote! { const },
+                quote! { value as u128 },
+                quote! { ::bitfield::access::fits(bits, #tr::BITS) },
+            ),
+            None => (
+                quote! {},
+                quote! { #tr::into_bits(value) },
+                quote! { #tr::in_bounds(bits) },
+            ),
+        };
+
+        // With `#[bitfield(repr = T)]` the setters take a `T`, which has to
+        // convert into the specifier's bucket.
+        let mut input = quote! { #tr::Bucket };
+        let mut convert = quote! {};
+        let mut convert_checked = quote! {};
+
+        if let Some(repr) = &field.repr {
+            constness = quote! {};
+            input = quote! { #repr };
+
+            let try_from = quote! {
+                <#tr::Bucket as ::std::convert::TryFrom<#repr>>::try_from(value)
+            };
+            // Like a value too wide for the field, one that does not convert
+            // only fails a debug assertion. There is nothing to truncate it
+            // to, so release builds leave the field as it is.
+            convert = quote! {
+                let value = match #try_from {
+                    ::std::result::Result::Ok(value) => value,
+                    ::std::result::Result::Err(_) => {
+                        debug_assert!(false, #msg);
+                        return;
                     }
+                };
+            };
+            convert_checked = quote! {
+                let value = match #try_from {
+                    ::std::result::Result::Ok(value) => value,
+                    ::std::result::Result::Err(_) => {
+                        return ::std::result::Result::Err(::bitfield::OutOfBounds);
+                    }
+                };
+            };
+        }
+
+        // Array fields take the index of the element before the value.
+        let (index, offset) = match &field.len {
+            Some(len) => {
+                let msg = format!("index out of bounds for field `{}`", &field.name);
+
+                (
+                    quote! { index: usize, },
+                    quote! {
+                        assert!(index < #len, #msg);
+                        let offset = OFFSET + index * #tr::BITS;
+                    },
+                )
+            }
+            None => (quote! {}, quote! { let offset = OFFSET; }),
+        };
+        let index_arg = field.len.as_ref().map(|_| quote! { index, });
+        let write = config.write(
+            quote! { self.data },
+            quote! { offset },
+            quote! { #tr::BITS },
+            quote! { bits },
+        );
+
+        tokens.extend(quote! {
+            #vis #constness fn #method(&mut self, #index value: #input) {
+                const OFFSET: usize = #pad;
+                #offset
+
+                #convert
+
+                let bits = #into_bits;
+                debug_assert!(#in_bounds, #msg);
+
+                #write
+            }
+
+            #vis #constness fn #checked(
+                &mut self,
+                #index
+                value: #input,
+            ) -> ::std::result::Result<(), ::bitfield::OutOfBounds> {
+                const OFFSET: usize = #pad;
+                #offset
+
+                #convert_checked
+
+                let bits = #into_bits;
+                if !#in_bounds {
+                    return ::std::result::Result::Err(::bitfield::OutOfBounds);
                 }
+
+                #write
+
+                ::std::result::Result::Ok(())
+            }
+
+            #vis #constness fn #with(mut self, #index value: #input) -> Self {
+                self.#method(#index_arg value);
+
+                self
+            }
+        });
+    }
+
+    tokens
+}
+
+/// Statements storing the values of `#[reserved(value = ...)]` fields in
+/// `data`, run by `new`.
+pub fn reserved(fields: &[Field], config: &Config) -> TokenStream {
+    let layout = config.layout();
+    let mut tokens = TokenStream::default();
+    for (cur, field) in fields.iter().enumerate() {
+        let Some(value) = &field.reserved else {
+            continue;
+        };
+
+        let pad = fields::offset(fields, cur);
+        let bits = field.bits();
+        let msg = format!("reserved value does not fit in field `{}`", &field.name);
+
+        tokens.extend(quote! {
+            {
+                const OFFSET: usize = #pad;
+                const VALUE: u128 = (#value) as u128;
+                const _: () = assert!(::bitfield::access::fits(VALUE, #bits), #msg);
+
+                ::bitfield::access::write(&mut data, OFFSET, #bits, #layout, VALUE);
             }
         });
     }
@@ -1,58 +1,192 @@
-use crate::fields::Field;
-use proc_macro2::TokenStream;
-use quote::{quote, ToTokens};
+use crate::{
+    config::Config,
+    fields::{self, Field},
+};
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, quote_spanned, ToTokens};
+use syn::spanned::Spanned;
 use syn::Ident;
 
-pub fn byte_size(fields: &[Field]) -> TokenStream {
+pub fn bit_size(fields: &[Field]) -> TokenStream {
     if fields.is_empty() {
         quote! { 0 }
+    } else if fields::placed(fields) {
+        // Explicitly placed fields may come in any order, so the struct ends
+        // where the last of them does.
+        let ends = ends(fields);
+        quote! {
+            {
+                let mut bits = 0;
+                #(
+                    let end = #ends;
+                    if end > bits {
+                        bits = end;
+                    }
+                )*
+                bits
+            }
+        }
     } else {
-        let s = fields.iter().map(|f| &f.ty);
+        let s = fields.iter().map(Field::bits);
         quote! {
-            (#(<#s as ::bitfield::Specifier>::BITS)+*) / 8
+            (#(#s)+*)
         }
     }
 }
 
-pub fn size_check(name: &Ident, fields: &[Field]) -> TokenStream {
-    if fields.is_empty() {
+pub fn byte_size(fields: &[Field], config: &Config) -> TokenStream {
+    let bits = bit_size(fields);
+
+    if let Some(repr) = &config.repr {
+        quote! { ::std::mem::size_of::<::std::primitive::#repr>() }
+    } else if config.filled {
+        quote! { #bits / 8 }
+    } else {
+        quote! { ::std::primitive::usize::div_ceil(#bits, 8) }
+    }
+}
+
+pub fn size_check(name: &Ident, fields: &[Field], config: &Config) -> TokenStream {
+    if let Some(repr) = &config.repr {
+        let bits = bit_size(fields);
+        let (cmp, message) = if config.filled {
+            (quote! { == }, format!("{name} does not fill a {repr}"))
+        } else {
+            (quote! { <= }, format!("{name} does not fit in a {repr}"))
+        };
+
+        quote! {
+            const _: () = assert!(#bits #cmp ::std::primitive::#repr::BITS as usize, #message);
+        }
+    } else if fields.is_empty() || !config.filled {
         quote! {}
     } else {
-        let s = fields.iter().map(|f| &f.ty);
+        let bits = bit_size(fields);
         let message = format!("{name} does not fill all bytes");
 
         quote! {
-            const _: () = assert!(( #(<#s as ::bitfield::Specifier>::BITS)+* ) % 8 == 0, #message);
+            const _: () = assert!(#bits % 8 == 0, #message);
         }
     }
 }
 
+/// Checks that every field, or every element of an array field, fits in the
+/// `u128` that accessors pass values around in. This also rejects nesting a
+/// bitfield wider than 128 bits.
+pub fn field_width_check(fields: &[Field]) -> TokenStream {
+    let mut tokens = TokenStream::default();
+
+    for field in fields {
+        let ty = &field.ty;
+        let msg = format!("field `{}` is wider than 128 bits", field.name);
+
+        tokens.extend(quote! {
+            const _: () = assert!(<#ty as ::bitfield::Specifier>::BITS <= 128, #msg);
+        });
+
+        // `new` writes reserved values in one go, array or not.
+        if field.reserved.is_some() && field.len.is_some() {
+            let bits = field.bits();
+            let msg = format!("reserved field `{}` is wider than 128 bits", field.name);
+
+            tokens.extend(quote! {
+                const _: () = assert!(#bits <= 128, #msg);
+            });
+        }
+    }
+
+    tokens
+}
+
+/// Checks the widths given by `#[bits = N]` against the field types.
+///
+/// Rather than a `const` assertion, whose error would point at the macro,
+/// each check assigns an array of the field's width to an array of the
+/// declared width. A mismatch is then a type error spanned to the attribute
+/// that states both widths.
 pub fn size_constraint(fields: &[Field]) -> TokenStream {
     let mut tokens = TokenStream::default();
 
     for field in fields {
         if let Some(expr) = &field.constraint {
-            let ty = &field.ty;
+            tokens.extend(width_check(expr.span(), expr.to_token_stream(), field.bits()));
+        }
+    }
 
-            let ty_repr = {
-                let mut t = TokenStream::default();
-                ty.to_tokens(&mut t);
-                t.to_string()
-            };
-            let expr_repr = {
-                let mut t = TokenStream::default();
-                expr.to_tokens(&mut t);
-                t.to_string()
-            };
+    tokens
+}
 
-            let msg = format!("expected {ty_repr} to be of size {expr_repr}");
+fn width_check(span: Span, declared: TokenStream, bits: TokenStream) -> TokenStream {
+    quote_spanned! {span=>
+        const _: [(); #declared] = [(); #bits];
+    }
+}
 
-            tokens.extend(quote! {
-                const _: () = assert!(#expr == <#ty as ::bitfield::Specifier>::BITS, #msg);
+fn starts(fields: &[Field]) -> Vec<TokenStream> {
+    (0..fields.len()).map(|cur| fields::offset(fields, cur)).collect()
+}
+
+fn ends(fields: &[Field]) -> Vec<TokenStream> {
+    let bits = fields.iter().map(Field::bits);
+    starts(fields)
+        .into_iter()
+        .zip(bits)
+        .map(|(start, bits)| quote! { #start + #bits })
+        .collect()
+}
+
+/// Checks that explicitly placed fields match the width of their range, and
+/// that together the fields cover every bit exactly once.
+pub fn placement_check(name: &Ident, fields: &[Field]) -> TokenStream {
+    if !fields::placed(fields) {
+        return quote! {};
+    }
+
+    let mut checks = TokenStream::default();
 
+    let mut widths = TokenStream::default();
+
+    for field in fields {
+        if let Some((width, span)) = &field.range_width {
+            widths.extend(width_check(*span, width.clone(), field.bits()));
+        }
+    }
+
+    let first = (0..fields.len()).map(|i| quote! { starts[#i] == 0 });
+    let msg = format!("{name} has a gap at bit 0");
+    checks.extend(quote! {
+        assert!(#(#first)||*, #msg);
+    });
+
+    for (i, a) in fields.iter().enumerate() {
+        for (j, b) in fields.iter().enumerate().skip(i + 1) {
+            let msg = format!("fields `{}` and `{}` overlap", a.name, b.name);
+            checks.extend(quote! {
+                assert!(ends[#i] <= starts[#j] || ends[#j] <= starts[#i], #msg);
             });
         }
+
+        let next = (0..fields.len()).map(|j| quote! { starts[#j] == ends[#i] });
+        let msg = format!("{name} has a gap after field `{}`", a.name);
+        checks.extend(quote! {
+            assert!(ends[#i] == BITS #(|| #next)*, #msg);
+        });
     }
 
-    tokens
+    let bits = bit_size(fields);
+    let starts = starts(fields);
+    let ends = ends(fields);
+    let len = fields.len();
+
+    quote! {
+        #widths
+
+        const _: () = {
+            const BITS: usize = #bits;
+            let starts: [usize; #len] = [#(#starts),*];
+            let ends: [usize; #len] = [#(#ends),*];
+
+            #checks
+        };
+    }
 }
@@ -0,0 +1,231 @@
+// Shift-and-mask helpers called by the accessors generated by #[bitfield].
+//
+// A field occupies `width` consecutive bits starting at bit `offset`. The
+// `Layout` decides how those bit numbers map onto the bytes, and in which order
+// the bits of the value are laid out across them. The helpers walk the field
+// one byte at a time instead of one bit at a time; since the generated
+// accessors always pass constant offsets, widths and layouts, the loops unroll
+// into a handful of shifts after optimization.
+
+/// How bit numbers map onto the bits of each byte.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BitOrder {
+    /// Bit 0 is the least significant bit of the first byte.
+    Lsb0,
+    /// Bit 0 is the most significant bit of the first byte.
+    Msb0,
+}
+
+/// The order in which a field's value is spread across its bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Endian {
+    /// The most significant bit of the value comes first.
+    Big,
+    /// The least significant bit of the value comes first.
+    Little,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Layout {
+    pub bits: BitOrder,
+    pub endian: Endian,
+}
+
+impl Layout {
+    /// Whether the bit order and endianness disagree, so that the field's
+    /// bits have to be reversed on their way in and out.
+    const fn reversed(self) -> bool {
+        matches!(
+            (self.bits, self.endian),
+            (BitOrder::Lsb0, Endian::Big) | (BitOrder::Msb0, Endian::Little)
+        )
+    }
+}
+
+const fn mask(width: usize) -> u64 {
+    if width >= 64 {
+        u64::MAX
+    } else {
+        (1 << width) - 1
+    }
+}
+
+/// Whether `value` fits in `width` bits.
+#[inline]
+pub const fn fits(value: u128, width: usize) -> bool {
+    width >= 128 || value >> width == 0
+}
+
+/// Sign-extends the low `width` bits of `value`.
+#[inline]
+pub const fn sign_extend(value: u128, width: usize) -> i128 {
+    let shift = 128 - width;
+    ((value << shift) as i128) >> shift
+}
+
+/// Whether `value`, a sign-extended two's complement integer, fits in `width`
+/// bits.
+#[inline]
+pub const fn fits_signed(value: u128, width: usize) -> bool {
+    sign_extend(value, width) as u128 == value
+}
+
+/// `REVERSED[b]` is `b` with its bits in the opposite order.
+const REVERSED: [u8; 256] = {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        table[i] = (i as u8).reverse_bits();
+        i += 1;
+    }
+    table
+};
+
+/// Reverses the low `width` bits of `value`, one byte at a time through a
+/// lookup table, which beats `u64::reverse_bits` on targets without a native
+/// bit reversal instruction.
+const fn reverse(value: u64, width: usize) -> u64 {
+    let bytes = width.div_ceil(8);
+    let mut reversed = 0;
+    let mut i = 0;
+    while i < bytes {
+        reversed = (reversed << 8) | REVERSED[(value >> (i * 8)) as u8 as usize] as u64;
+        i += 1;
+    }
+
+    reversed >> (bytes * 8 - width)
+}
+
+/// Reads the `width`-bit field starting at bit `offset` of `data`.
+#[inline]
+pub const fn read<const N: usize>(
+    data: &[u8; N],
+    offset: usize,
+    width: usize,
+    layout: Layout,
+) -> u128 {
+    debug_assert!(width <= 128, "fields wider than 128 bits are not supported");
+
+    if width <= 64 {
+        return read64(data, offset, width, layout) as u128;
+    }
+
+    // Wider fields are read as two halves: the low 64 bits of the value come
+    // first with `Little` and last with `Big`.
+    let high = width - 64;
+    match layout.endian {
+        Endian::Little => {
+            let low = read64(data, offset, 64, layout) as u128;
+            low | (read64(data, offset + 64, high, layout) as u128) << 64
+        }
+        Endian::Big => {
+            let low = read64(data, offset + high, 64, layout) as u128;
+            low | (read64(data, offset, high, layout) as u128) << 64
+        }
+    }
+}
+
+/// Writes the low `width` bits of `value` to the field starting at bit
+/// `offset` of `data`, leaving all other bits untouched.
+#[inline]
+pub const fn write<const N: usize>(
+    data: &mut [u8; N],
+    offset: usize,
+    width: usize,
+    layout: Layout,
+    value: u128,
+) {
+    debug_assert!(width <= 128, "fields wider than 128 bits are not supported");
+
+    if width <= 64 {
+        write64(data, offset, width, layout, value as u64);
+        return;
+    }
+
+    let high = width - 64;
+    let (low_offset, high_offset) = match layout.endian {
+        Endian::Little => (offset, offset + 64),
+        Endian::Big => (offset + high, offset),
+    };
+
+    write64(data, low_offset, 64, layout, value as u64);
+    write64(data, high_offset, high, layout, (value >> 64) as u64);
+}
+
+/// `read` for fields of at most 64 bits, which span at most 9 bytes and can be
+/// gathered into a single `u128`.
+#[inline]
+const fn read64<const N: usize>(
+    data: &[u8; N],
+    offset: usize,
+    width: usize,
+    layout: Layout,
+) -> u64 {
+    let first = offset / 8;
+    let last = (offset + width).div_ceil(8);
+    let span = (last - first) * 8;
+
+    let mut word: u128 = 0;
+    let mut i = first;
+    while i < last {
+        word |= (data[i] as u128) << byte_shift(i - first, span, layout);
+        i += 1;
+    }
+
+    let window = (word >> field_shift(offset, width, span, layout)) as u64 & mask(width);
+
+    if layout.reversed() {
+        reverse(window, width)
+    } else {
+        window
+    }
+}
+
+/// `write` for fields of at most 64 bits.
+#[inline]
+const fn write64<const N: usize>(
+    data: &mut [u8; N],
+    offset: usize,
+    width: usize,
+    layout: Layout,
+    value: u64,
+) {
+    let first = offset / 8;
+    let last = (offset + width).div_ceil(8);
+    let span = (last - first) * 8;
+
+    let window = if layout.reversed() {
+        reverse(value & mask(width), width)
+    } else {
+        value & mask(width)
+    };
+
+    let shift = field_shift(offset, width, span, layout);
+    let bits = (window as u128) << shift;
+    let keep = !((mask(width) as u128) << shift);
+
+    let mut i = first;
+    while i < last {
+        let shift = byte_shift(i - first, span, layout);
+        data[i] = (data[i] & (keep >> shift) as u8) | (bits >> shift) as u8;
+        i += 1;
+    }
+}
+
+/// Where the `index`-th byte of a field's `span` sits in the word gathered
+/// from those bytes. With `Msb0` the first byte is the most significant one.
+const fn byte_shift(index: usize, span: usize, layout: Layout) -> usize {
+    match layout.bits {
+        BitOrder::Lsb0 => index * 8,
+        BitOrder::Msb0 => span - (index + 1) * 8,
+    }
+}
+
+/// Where a field starting at bit `offset` sits in the word gathered from the
+/// bytes it spans.
+const fn field_shift(offset: usize, width: usize, span: usize, layout: Layout) -> usize {
+    match layout.bits {
+        BitOrder::Lsb0 => offset % 8,
+        BitOrder::Msb0 => span - offset % 8 - width,
+    }
+}
@@ -12,32 +12,107 @@
 // (macro, trait, struct) through the one bitfield crate.
 pub use bitfield_impl::{bitfield, BitfieldSpecifier};
 
+use std::fmt;
+
+#[doc(hidden)]
+pub mod access;
+
 pub trait Specifier {
+    /// The type taken by setters.
     type Bucket;
+    /// The type returned by getters. This is `Bucket` itself, unless some
+    /// patterns of `BITS` bits have no corresponding `Bucket`.
+    type Output;
     const BITS: usize;
 
-    fn is_set(bucket: &Self::Bucket, bit: usize) -> bool;
-    fn set(bucket: &mut Self::Bucket, bit: usize);
-    fn empty() -> Self::Bucket;
+    /// The raw bits of `bucket`, right-aligned.
+    fn into_bits(bucket: Self::Bucket) -> u128;
+    /// Rebuilds a value from `BITS` right-aligned raw bits.
+    fn from_bits(bits: u128) -> Self::Output;
+
+    /// Whether the output of `into_bits` fits in `BITS` bits, i.e. whether
+    /// storing it would not truncate the value.
+    fn in_bounds(bits: u128) -> bool {
+        access::fits(bits, Self::BITS)
+    }
+}
+
+/// Returned by the `set_*_checked` setters when a value does not fit in its
+/// field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutOfBounds;
+
+impl fmt::Display for OutOfBounds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("value does not fit in the bitfield")
+    }
+}
+
+impl std::error::Error for OutOfBounds {}
+
+/// Returned by the getters of enums declared with `#[bits = N]` when the
+/// stored bits do not match any variant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidBitPattern {
+    raw: u64,
+}
+
+impl InvalidBitPattern {
+    pub fn new(raw: u64) -> InvalidBitPattern {
+        InvalidBitPattern { raw }
+    }
+
+    /// The bits that were found in the field.
+    pub fn raw_value(&self) -> u64 {
+        self.raw
+    }
+}
+
+impl fmt::Display for InvalidBitPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#b} does not match any variant", self.raw)
+    }
 }
 
+impl std::error::Error for InvalidBitPattern {}
+
 macro_rules! b {
     ($ident: ident, $lit: literal, $bucket: ident) => {
         pub enum $ident {}
         impl Specifier for $ident {
             type Bucket = $bucket;
+            type Output = $bucket;
+            const BITS: usize = $lit;
+
+            fn into_bits(bucket: Self::Bucket) -> u128 {
+                bucket as u128
+            }
+
+            fn from_bits(bits: u128) -> Self::Output {
+                bits as $bucket
+            }
+        }
+    };
+}
+
+macro_rules! i {
+    ($ident: ident, $lit: literal, $bucket: ident) => {
+        pub enum $ident {}
+        impl Specifier for $ident {
+            type Bucket = $bucket;
+            type Output = $bucket;
             const BITS: usize = $lit;
 
-            fn is_set(bucket: &Self::Bucket, bit: usize) -> bool {
-                bucket & (1 << bit) != 0
+            fn into_bits(bucket: Self::Bucket) -> u128 {
+                bucket as u128
             }
 
-            fn set(bucket: &mut Self::Bucket, bit: usize) {
-                *bucket |= (1 << bit);
+            fn from_bits(bits: u128) -> Self::Output {
+                access::sign_extend(bits, $lit) as $bucket
             }
 
-            fn empty() -> Self::Bucket {
-                0
+            fn in_bounds(bits: u128) -> bool {
+                access::fits_signed(bits, $lit)
             }
         }
     };
@@ -45,21 +120,20 @@ macro_rules! b {
 
 impl Specifier for bool {
     type Bucket = Self;
+    type Output = Self;
     const BITS: usize = 1;
 
-    fn is_set(bucket: &Self::Bucket, _: usize) -> bool {
-        *bucket
+    fn into_bits(bucket: Self::Bucket) -> u128 {
+        bucket as u128
     }
 
-    fn set(bucket: &mut Self::Bucket, _: usize) {
-        *bucket = true;
-    }
-
-    fn empty() -> Self::Bucket {
-        false
+    fn from_bits(bits: u128) -> Self::Output {
+        bits != 0
     }
 }
 
+// The macro recognizes `B1` to `B128`, `I2` to `I64` and `bool` by name, to
+// generate `const` accessors for them. See `Builtin` in the impl crate.
 b!(B1, 1, u8);
 b!(B2, 2, u8);
 b!(B3, 3, u8);
@@ -94,7 +168,7 @@ b!(B29, 29, u32);
 
 b!(B30, 30, u32);
 b!(B31, 31, u32);
-b!(B32, 32, u64);
+b!(B32, 32, u32);
 b!(B33, 33, u64);
 b!(B34, 34, u64);
 b!(B35, 35, u64);
@@ -130,4 +204,144 @@ b!(B61, 61, u64);
 b!(B62, 62, u64);
 b!(B63, 63, u64);
 b!(B64, 64, u64);
+b!(B65, 65, u128);
+b!(B66, 66, u128);
+b!(B67, 67, u128);
+b!(B68, 68, u128);
+b!(B69, 69, u128);
+
+b!(B70, 70, u128);
+b!(B71, 71, u128);
+b!(B72, 72, u128);
+b!(B73, 73, u128);
+b!(B74, 74, u128);
+b!(B75, 75, u128);
+b!(B76, 76, u128);
+b!(B77, 77, u128);
+b!(B78, 78, u128);
+b!(B79, 79, u128);
+
+b!(B80, 80, u128);
+b!(B81, 81, u128);
+b!(B82, 82, u128);
+b!(B83, 83, u128);
+b!(B84, 84, u128);
+b!(B85, 85, u128);
+b!(B86, 86, u128);
+b!(B87, 87, u128);
+b!(B88, 88, u128);
+b!(B89, 89, u128);
+
+b!(B90, 90, u128);
+b!(B91, 91, u128);
+b!(B92, 92, u128);
+b!(B93, 93, u128);
+b!(B94, 94, u128);
+b!(B95, 95, u128);
+b!(B96, 96, u128);
+b!(B97, 97, u128);
+b!(B98, 98, u128);
+b!(B99, 99, u128);
+
+b!(B100, 100, u128);
+b!(B101, 101, u128);
+b!(B102, 102, u128);
+b!(B103, 103, u128);
+b!(B104, 104, u128);
+b!(B105, 105, u128);
+b!(B106, 106, u128);
+b!(B107, 107, u128);
+b!(B108, 108, u128);
+b!(B109, 109, u128);
+
+b!(B110, 110, u128);
+b!(B111, 111, u128);
+b!(B112, 112, u128);
+b!(B113, 113, u128);
+b!(B114, 114, u128);
+b!(B115, 115, u128);
+b!(B116, 116, u128);
+b!(B117, 117, u128);
+b!(B118, 118, u128);
+b!(B119, 119, u128);
+
+b!(B120, 120, u128);
+b!(B121, 121, u128);
+b!(B122, 122, u128);
+b!(B123, 123, u128);
+b!(B124, 124, u128);
+b!(B125, 125, u128);
+b!(B126, 126, u128);
+b!(B127, 127, u128);
+b!(B128, 128, u128);
+
+i!(I2, 2, i8);
+i!(I3, 3, i8);
+i!(I4, 4, i8);
+i!(I5, 5, i8);
+i!(I6, 6, i8);
+i!(I7, 7, i8);
+i!(I8, 8, i8);
+i!(I9, 9, i16);
+
+i!(I10, 10, i16);
+i!(I11, 11, i16);
+i!(I12, 12, i16);
+i!(I13, 13, i16);
+i!(I14, 14, i16);
+i!(I15, 15, i16);
+i!(I16, 16, i16);
+i!(I17, 17, i32);
+i!(I18, 18, i32);
+i!(I19, 19, i32);
+
+i!(I20, 20, i32);
+i!(I21, 21, i32);
+i!(I22, 22, i32);
+i!(I23, 23, i32);
+i!(I24, 24, i32);
+i!(I25, 25, i32);
+i!(I26, 26, i32);
+i!(I27, 27, i32);
+i!(I28, 28, i32);
+i!(I29, 29, i32);
+
+i!(I30, 30, i32);
+i!(I31, 31, i32);
+i!(I32, 32, i32);
+i!(I33, 33, i64);
+i!(I34, 34, i64);
+i!(I35, 35, i64);
+i!(I36, 36, i64);
+i!(I37, 37, i64);
+i!(I38, 38, i64);
+i!(I39, 39, i64);
+
+i!(I40, 40, i64);
+i!(I41, 41, i64);
+i!(I42, 42, i64);
+i!(I43, 43, i64);
+i!(I44, 44, i64);
+i!(I45, 45, i64);
+i!(I46, 46, i64);
+i!(I47, 47, i64);
+i!(I48, 48, i64);
+i!(I49, 49, i64);
+
+i!(I50, 50, i64);
+i!(I51, 51, i64);
+i!(I52, 52, i64);
+i!(I53, 53, i64);
+i!(I54, 54, i64);
+i!(I55, 55, i64);
+i!(I56, 56, i64);
+i!(I57, 57, i64);
+i!(I58, 58, i64);
+i!(I59, 59, i64);
+
+i!(I60, 60, i64);
+i!(I61, 61, i64);
+i!(I62, 62, i64);
+i!(I63, 63, i64);
+i!(I64, 64, i64);
 // TODO other things
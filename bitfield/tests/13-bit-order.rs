@@ -0,0 +1,57 @@
+// By default bits are numbered from the least significant bit of the first
+// byte, and each field's value is stored most significant bit first. Wire
+// formats and hardware registers often use other conventions, so both can be
+// picked on the attribute:
+//
+//     #[bitfield(bits = "msb0", endian = "big")]
+//
+// `bits` selects whether bit 0 is the least ("lsb0") or most ("msb0")
+// significant bit of the first byte, and `endian` whether a field's value is
+// laid out starting from its most ("big") or least ("little") significant
+// bit.
+//
+// With "msb0" and "big" fields read left to right exactly as they are drawn in
+// network protocol diagrams such as the IPv4 header:
+//
+//      0                   1                   2                   3
+//      0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+//     +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+//     |Version|  IHL  |Type of Service|          Total Length         |
+//     +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+
+use bitfield::*;
+
+#[bitfield(bits = "msb0", endian = "big")]
+pub struct Ipv4Start {
+    version: B4,
+    ihl: B4,
+    tos: B8,
+    total_length: B16,
+}
+
+#[bitfield(bits = "lsb0", endian = "little")]
+pub struct Register {
+    enable: bool,
+    mode: B3,
+    divider: B12,
+}
+
+fn main() {
+    let mut header = Ipv4Start::new();
+    header.set_version(4);
+    header.set_ihl(5);
+    header.set_total_length(0x0054);
+    assert_eq!(header.as_bytes(), &[0x45, 0x00, 0x00, 0x54]);
+    assert_eq!(header.get_version(), 4);
+    assert_eq!(header.get_ihl(), 5);
+    assert_eq!(header.get_total_length(), 0x0054);
+
+    let mut register = Register::new();
+    register.set_enable(true);
+    register.set_mode(0b110);
+    register.set_divider(0xabc);
+    assert_eq!(register.as_bytes(), &[0xcd, 0xab]);
+    assert_eq!(register.get_enable(), true);
+    assert_eq!(register.get_mode(), 0b110);
+    assert_eq!(register.get_divider(), 0xabc);
+}
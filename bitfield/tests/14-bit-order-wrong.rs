@@ -0,0 +1,11 @@
+// Only "lsb0" and "msb0" are valid bit orders.
+
+use bitfield::*;
+
+#[bitfield(bits = "msb")]
+pub struct Header {
+    version: B4,
+    ihl: B4,
+}
+
+fn main() {}
@@ -0,0 +1,40 @@
+// Bitfields are usually parsed straight out of a buffer, so provide
+// conversions between the struct and its raw bytes.
+//
+//     impl MyFourBytes {
+//         pub const fn from_bytes(bytes: [u8; 4]) -> Self;
+//         pub const fn into_bytes(self) -> [u8; 4];
+//         pub const fn as_bytes(&self) -> &[u8; 4];
+//     }
+//
+//     impl TryFrom<&[u8]> for MyFourBytes { ... }
+//
+// The TryFrom impl fails when the slice is not exactly as long as the struct.
+
+use bitfield::*;
+use std::convert::TryFrom;
+
+#[bitfield]
+pub struct MyFourBytes {
+    a: B1,
+    b: B3,
+    c: B4,
+    d: B24,
+}
+
+fn main() {
+    let bitfield = MyFourBytes::from_bytes([0b1110_0101, 0, 0, 0x80]);
+    assert_eq!(bitfield.get_a(), 1);
+    assert_eq!(bitfield.get_b(), 0b010);
+    assert_eq!(bitfield.get_c(), 0b0111);
+    assert_eq!(bitfield.get_d(), 1);
+    assert_eq!(bitfield.as_bytes(), &[0b1110_0101, 0, 0, 0x80]);
+    assert_eq!(bitfield.into_bytes(), [0b1110_0101, 0, 0, 0x80]);
+
+    let packet = [0xff, 0x01, 0x02, 0x03, 0x04, 0x05];
+    let bitfield = MyFourBytes::try_from(&packet[1..5]).unwrap();
+    assert_eq!(bitfield.as_bytes(), &[0x01, 0x02, 0x03, 0x04]);
+
+    assert!(MyFourBytes::try_from(&packet[..]).is_err());
+    assert!(MyFourBytes::try_from(&packet[..3]).is_err());
+}
@@ -0,0 +1,37 @@
+// Setters used to silently drop the high bits of a value that is too wide for
+// its field. Now `set_<field>` debug-asserts that the value fits, and
+// `set_<field>_checked` reports it as an error instead of writing anything.
+//
+//     impl EdgeCaseBytes {
+//         pub fn set_a_checked(&mut self, value: u16) -> Result<(), bitfield::OutOfBounds>;
+//     }
+
+use bitfield::*;
+
+#[bitfield]
+pub struct EdgeCaseBytes {
+    a: B9,
+    b: B6,
+    c: B13,
+    d: B4,
+}
+
+fn main() {
+    let mut bitfield = EdgeCaseBytes::new();
+
+    assert_eq!(bitfield.set_a_checked(511), Ok(()));
+    assert_eq!(bitfield.get_a(), 511);
+
+    assert_eq!(bitfield.set_a_checked(512), Err(OutOfBounds));
+    assert_eq!(bitfield.get_a(), 511);
+    assert_eq!(bitfield.get_b(), 0);
+
+    assert_eq!(bitfield.set_d_checked(16), Err(OutOfBounds));
+    assert_eq!(bitfield.set_d_checked(15), Ok(()));
+    assert_eq!(bitfield.get_d(), 15);
+
+    if cfg!(debug_assertions) {
+        let result = std::panic::catch_unwind(move || bitfield.set_b(64));
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,604 @@
+// Enums with more than 256 variants need more than 8 bits, and nothing
+// requires the first variant to have discriminant 0. Every discriminant in
+// 0..2^BITS must still round-trip through the bitfield.
+//
+// A 64-bit enum may also have negative discriminants, which are stored as
+// their two's complement.
+
+use bitfield::*;
+
+#[bitfield]
+pub struct Instruction {
+    opcode: Opcode,
+    flag: bool,
+    operand: B6,
+}
+
+#[derive(BitfieldSpecifier, Debug, PartialEq)]
+pub enum Shuffled {
+    Two = 2,
+    Three = 3,
+    Zero = 0,
+    One = 1,
+}
+
+#[bitfield]
+pub struct Pair {
+    first: Shuffled,
+    second: Shuffled,
+    rest: B4,
+}
+
+#[derive(BitfieldSpecifier, Debug, PartialEq)]
+#[repr(i64)]
+#[bits = 64]
+pub enum Wide {
+    Neg = -1,
+    Min = i64::MIN,
+    Zero = 0,
+}
+
+#[bitfield]
+pub struct WideField {
+    w: Wide,
+}
+
+#[derive(BitfieldSpecifier, Debug, PartialEq)]
+pub enum Opcode {
+    Code0 = 5,
+    Code1 = 42,
+    Code2 = 79,
+    Code3 = 116,
+    Code4 = 153,
+    Code5 = 190,
+    Code6 = 227,
+    Code7 = 264,
+    Code8 = 301,
+    Code9 = 338,
+    Code10 = 375,
+    Code11 = 412,
+    Code12 = 449,
+    Code13 = 486,
+    Code14 = 11,
+    Code15 = 48,
+    Code16 = 85,
+    Code17 = 122,
+    Code18 = 159,
+    Code19 = 196,
+    Code20 = 233,
+    Code21 = 270,
+    Code22 = 307,
+    Code23 = 344,
+    Code24 = 381,
+    Code25 = 418,
+    Code26 = 455,
+    Code27 = 492,
+    Code28 = 17,
+    Code29 = 54,
+    Code30 = 91,
+    Code31 = 128,
+    Code32 = 165,
+    Code33 = 202,
+    Code34 = 239,
+    Code35 = 276,
+    Code36 = 313,
+    Code37 = 350,
+    Code38 = 387,
+    Code39 = 424,
+    Code40 = 461,
+    Code41 = 498,
+    Code42 = 23,
+    Code43 = 60,
+    Code44 = 97,
+    Code45 = 134,
+    Code46 = 171,
+    Code47 = 208,
+    Code48 = 245,
+    Code49 = 282,
+    Code50 = 319,
+    Code51 = 356,
+    Code52 = 393,
+    Code53 = 430,
+    Code54 = 467,
+    Code55 = 504,
+    Code56 = 29,
+    Code57 = 66,
+    Code58 = 103,
+    Code59 = 140,
+    Code60 = 177,
+    Code61 = 214,
+    Code62 = 251,
+    Code63 = 288,
+    Code64 = 325,
+    Code65 = 362,
+    Code66 = 399,
+    Code67 = 436,
+    Code68 = 473,
+    Code69 = 510,
+    Code70 = 35,
+    Code71 = 72,
+    Code72 = 109,
+    Code73 = 146,
+    Code74 = 183,
+    Code75 = 220,
+    Code76 = 257,
+    Code77 = 294,
+    Code78 = 331,
+    Code79 = 368,
+    Code80 = 405,
+    Code81 = 442,
+    Code82 = 479,
+    Code83 = 4,
+    Code84 = 41,
+    Code85 = 78,
+    Code86 = 115,
+    Code87 = 152,
+    Code88 = 189,
+    Code89 = 226,
+    Code90 = 263,
+    Code91 = 300,
+    Code92 = 337,
+    Code93 = 374,
+    Code94 = 411,
+    Code95 = 448,
+    Code96 = 485,
+    Code97 = 10,
+    Code98 = 47,
+    Code99 = 84,
+    Code100 = 121,
+    Code101 = 158,
+    Code102 = 195,
+    Code103 = 232,
+    Code104 = 269,
+    Code105 = 306,
+    Code106 = 343,
+    Code107 = 380,
+    Code108 = 417,
+    Code109 = 454,
+    Code110 = 491,
+    Code111 = 16,
+    Code112 = 53,
+    Code113 = 90,
+    Code114 = 127,
+    Code115 = 164,
+    Code116 = 201,
+    Code117 = 238,
+    Code118 = 275,
+    Code119 = 312,
+    Code120 = 349,
+    Code121 = 386,
+    Code122 = 423,
+    Code123 = 460,
+    Code124 = 497,
+    Code125 = 22,
+    Code126 = 59,
+    Code127 = 96,
+    Code128 = 133,
+    Code129 = 170,
+    Code130 = 207,
+    Code131 = 244,
+    Code132 = 281,
+    Code133 = 318,
+    Code134 = 355,
+    Code135 = 392,
+    Code136 = 429,
+    Code137 = 466,
+    Code138 = 503,
+    Code139 = 28,
+    Code140 = 65,
+    Code141 = 102,
+    Code142 = 139,
+    Code143 = 176,
+    Code144 = 213,
+    Code145 = 250,
+    Code146 = 287,
+    Code147 = 324,
+    Code148 = 361,
+    Code149 = 398,
+    Code150 = 435,
+    Code151 = 472,
+    Code152 = 509,
+    Code153 = 34,
+    Code154 = 71,
+    Code155 = 108,
+    Code156 = 145,
+    Code157 = 182,
+    Code158 = 219,
+    Code159 = 256,
+    Code160 = 293,
+    Code161 = 330,
+    Code162 = 367,
+    Code163 = 404,
+    Code164 = 441,
+    Code165 = 478,
+    Code166 = 3,
+    Code167 = 40,
+    Code168 = 77,
+    Code169 = 114,
+    Code170 = 151,
+    Code171 = 188,
+    Code172 = 225,
+    Code173 = 262,
+    Code174 = 299,
+    Code175 = 336,
+    Code176 = 373,
+    Code177 = 410,
+    Code178 = 447,
+    Code179 = 484,
+    Code180 = 9,
+    Code181 = 46,
+    Code182 = 83,
+    Code183 = 120,
+    Code184 = 157,
+    Code185 = 194,
+    Code186 = 231,
+    Code187 = 268,
+    Code188 = 305,
+    Code189 = 342,
+    Code190 = 379,
+    Code191 = 416,
+    Code192 = 453,
+    Code193 = 490,
+    Code194 = 15,
+    Code195 = 52,
+    Code196 = 89,
+    Code197 = 126,
+    Code198 = 163,
+    Code199 = 200,
+    Code200 = 237,
+    Code201 = 274,
+    Code202 = 311,
+    Code203 = 348,
+    Code204 = 385,
+    Code205 = 422,
+    Code206 = 459,
+    Code207 = 496,
+    Code208 = 21,
+    Code209 = 58,
+    Code210 = 95,
+    Code211 = 132,
+    Code212 = 169,
+    Code213 = 206,
+    Code214 = 243,
+    Code215 = 280,
+    Code216 = 317,
+    Code217 = 354,
+    Code218 = 391,
+    Code219 = 428,
+    Code220 = 465,
+    Code221 = 502,
+    Code222 = 27,
+    Code223 = 64,
+    Code224 = 101,
+    Code225 = 138,
+    Code226 = 175,
+    Code227 = 212,
+    Code228 = 249,
+    Code229 = 286,
+    Code230 = 323,
+    Code231 = 360,
+    Code232 = 397,
+    Code233 = 434,
+    Code234 = 471,
+    Code235 = 508,
+    Code236 = 33,
+    Code237 = 70,
+    Code238 = 107,
+    Code239 = 144,
+    Code240 = 181,
+    Code241 = 218,
+    Code242 = 255,
+    Code243 = 292,
+    Code244 = 329,
+    Code245 = 366,
+    Code246 = 403,
+    Code247 = 440,
+    Code248 = 477,
+    Code249 = 2,
+    Code250 = 39,
+    Code251 = 76,
+    Code252 = 113,
+    Code253 = 150,
+    Code254 = 187,
+    Code255 = 224,
+    Code256 = 261,
+    Code257 = 298,
+    Code258 = 335,
+    Code259 = 372,
+    Code260 = 409,
+    Code261 = 446,
+    Code262 = 483,
+    Code263 = 8,
+    Code264 = 45,
+    Code265 = 82,
+    Code266 = 119,
+    Code267 = 156,
+    Code268 = 193,
+    Code269 = 230,
+    Code270 = 267,
+    Code271 = 304,
+    Code272 = 341,
+    Code273 = 378,
+    Code274 = 415,
+    Code275 = 452,
+    Code276 = 489,
+    Code277 = 14,
+    Code278 = 51,
+    Code279 = 88,
+    Code280 = 125,
+    Code281 = 162,
+    Code282 = 199,
+    Code283 = 236,
+    Code284 = 273,
+    Code285 = 310,
+    Code286 = 347,
+    Code287 = 384,
+    Code288 = 421,
+    Code289 = 458,
+    Code290 = 495,
+    Code291 = 20,
+    Code292 = 57,
+    Code293 = 94,
+    Code294 = 131,
+    Code295 = 168,
+    Code296 = 205,
+    Code297 = 242,
+    Code298 = 279,
+    Code299 = 316,
+    Code300 = 353,
+    Code301 = 390,
+    Code302 = 427,
+    Code303 = 464,
+    Code304 = 501,
+    Code305 = 26,
+    Code306 = 63,
+    Code307 = 100,
+    Code308 = 137,
+    Code309 = 174,
+    Code310 = 211,
+    Code311 = 248,
+    Code312 = 285,
+    Code313 = 322,
+    Code314 = 359,
+    Code315 = 396,
+    Code316 = 433,
+    Code317 = 470,
+    Code318 = 507,
+    Code319 = 32,
+    Code320 = 69,
+    Code321 = 106,
+    Code322 = 143,
+    Code323 = 180,
+    Code324 = 217,
+    Code325 = 254,
+    Code326 = 291,
+    Code327 = 328,
+    Code328 = 365,
+    Code329 = 402,
+    Code330 = 439,
+    Code331 = 476,
+    Code332 = 1,
+    Code333 = 38,
+    Code334 = 75,
+    Code335 = 112,
+    Code336 = 149,
+    Code337 = 186,
+    Code338 = 223,
+    Code339 = 260,
+    Code340 = 297,
+    Code341 = 334,
+    Code342 = 371,
+    Code343 = 408,
+    Code344 = 445,
+    Code345 = 482,
+    Code346 = 7,
+    Code347 = 44,
+    Code348 = 81,
+    Code349 = 118,
+    Code350 = 155,
+    Code351 = 192,
+    Code352 = 229,
+    Code353 = 266,
+    Code354 = 303,
+    Code355 = 340,
+    Code356 = 377,
+    Code357 = 414,
+    Code358 = 451,
+    Code359 = 488,
+    Code360 = 13,
+    Code361 = 50,
+    Code362 = 87,
+    Code363 = 124,
+    Code364 = 161,
+    Code365 = 198,
+    Code366 = 235,
+    Code367 = 272,
+    Code368 = 309,
+    Code369 = 346,
+    Code370 = 383,
+    Code371 = 420,
+    Code372 = 457,
+    Code373 = 494,
+    Code374 = 19,
+    Code375 = 56,
+    Code376 = 93,
+    Code377 = 130,
+    Code378 = 167,
+    Code379 = 204,
+    Code380 = 241,
+    Code381 = 278,
+    Code382 = 315,
+    Code383 = 352,
+    Code384 = 389,
+    Code385 = 426,
+    Code386 = 463,
+    Code387 = 500,
+    Code388 = 25,
+    Code389 = 62,
+    Code390 = 99,
+    Code391 = 136,
+    Code392 = 173,
+    Code393 = 210,
+    Code394 = 247,
+    Code395 = 284,
+    Code396 = 321,
+    Code397 = 358,
+    Code398 = 395,
+    Code399 = 432,
+    Code400 = 469,
+    Code401 = 506,
+    Code402 = 31,
+    Code403 = 68,
+    Code404 = 105,
+    Code405 = 142,
+    Code406 = 179,
+    Code407 = 216,
+    Code408 = 253,
+    Code409 = 290,
+    Code410 = 327,
+    Code411 = 364,
+    Code412 = 401,
+    Code413 = 438,
+    Code414 = 475,
+    Code415 = 0,
+    Code416 = 37,
+    Code417 = 74,
+    Code418 = 111,
+    Code419 = 148,
+    Code420 = 185,
+    Code421 = 222,
+    Code422 = 259,
+    Code423 = 296,
+    Code424 = 333,
+    Code425 = 370,
+    Code426 = 407,
+    Code427 = 444,
+    Code428 = 481,
+    Code429 = 6,
+    Code430 = 43,
+    Code431 = 80,
+    Code432 = 117,
+    Code433 = 154,
+    Code434 = 191,
+    Code435 = 228,
+    Code436 = 265,
+    Code437 = 302,
+    Code438 = 339,
+    Code439 = 376,
+    Code440 = 413,
+    Code441 = 450,
+    Code442 = 487,
+    Code443 = 12,
+    Code444 = 49,
+    Code445 = 86,
+    Code446 = 123,
+    Code447 = 160,
+    Code448 = 197,
+    Code449 = 234,
+    Code450 = 271,
+    Code451 = 308,
+    Code452 = 345,
+    Code453 = 382,
+    Code454 = 419,
+    Code455 = 456,
+    Code456 = 493,
+    Code457 = 18,
+    Code458 = 55,
+    Code459 = 92,
+    Code460 = 129,
+    Code461 = 166,
+    Code462 = 203,
+    Code463 = 240,
+    Code464 = 277,
+    Code465 = 314,
+    Code466 = 351,
+    Code467 = 388,
+    Code468 = 425,
+    Code469 = 462,
+    Code470 = 499,
+    Code471 = 24,
+    Code472 = 61,
+    Code473 = 98,
+    Code474 = 135,
+    Code475 = 172,
+    Code476 = 209,
+    Code477 = 246,
+    Code478 = 283,
+    Code479 = 320,
+    Code480 = 357,
+    Code481 = 394,
+    Code482 = 431,
+    Code483 = 468,
+    Code484 = 505,
+    Code485 = 30,
+    Code486 = 67,
+    Code487 = 104,
+    Code488 = 141,
+    Code489 = 178,
+    Code490 = 215,
+    Code491 = 252,
+    Code492 = 289,
+    Code493 = 326,
+    Code494 = 363,
+    Code495 = 400,
+    Code496 = 437,
+    Code497 = 474,
+    Code498 = 511,
+    Code499 = 36,
+    Code500 = 73,
+    Code501 = 110,
+    Code502 = 147,
+    Code503 = 184,
+    Code504 = 221,
+    Code505 = 258,
+    Code506 = 295,
+    Code507 = 332,
+    Code508 = 369,
+    Code509 = 406,
+    Code510 = 443,
+    Code511 = 480,
+}
+
+fn main() {
+    assert_eq!(<Opcode as Specifier>::BITS, 9);
+    assert_eq!(std::mem::size_of::<Instruction>(), 2);
+
+    let mut instruction = Instruction::new();
+    assert_eq!(instruction.get_opcode(), Opcode::Code415);
+
+    instruction.set_operand(0b10_1010);
+    instruction.set_opcode(Opcode::Code0);
+    instruction.set_flag(true);
+    assert_eq!(instruction.get_opcode(), Opcode::Code0);
+    assert_eq!(instruction.get_flag(), true);
+    assert_eq!(instruction.get_operand(), 0b10_1010);
+
+    instruction.set_opcode(Opcode::Code498);
+    assert_eq!(Opcode::Code498 as u64, 511);
+    assert_eq!(instruction.get_opcode(), Opcode::Code498);
+    assert_eq!(instruction.get_operand(), 0b10_1010);
+
+    for bits in 0..512 {
+        instruction.set_opcode(<Opcode as Specifier>::from_bits(bits));
+        assert_eq!(<Opcode as Specifier>::into_bits(instruction.get_opcode()), bits);
+        assert_eq!(instruction.get_flag(), true);
+        assert_eq!(instruction.get_operand(), 0b10_1010);
+    }
+
+    // Only the low 9 bits name a variant.
+    assert_eq!(<Opcode as Specifier>::from_bits(1 << 9), Opcode::Code415);
+
+    let mut wide = WideField::new();
+    assert_eq!(wide.set_w_checked(Wide::Neg), Ok(()));
+    assert_eq!(wide.get_w(), Ok(Wide::Neg));
+    assert_eq!(wide.as_bytes(), &[0xff; 8]);
+    wide.set_w(Wide::Min);
+    assert_eq!(wide.get_w(), Ok(Wide::Min));
+
+    let mut pair = Pair::new();
+    assert_eq!(pair.get_first(), Shuffled::Zero);
+    pair.set_first(Shuffled::Three);
+    pair.set_second(Shuffled::Two);
+    assert_eq!(pair.get_first(), Shuffled::Three);
+    assert_eq!(pair.get_second(), Shuffled::Two);
+}
@@ -0,0 +1,58 @@
+// Enums whose number of variants is not a power of two can be used as
+// specifiers by giving their width explicitly with #[bits = N]. Since some
+// patterns of N bits then do not name a variant, the getter returns a Result
+// whose error gives access to the raw bits.
+//
+//     impl MyBitfield {
+//         pub fn get_small_prime(&self) -> Result<SmallPrime, bitfield::InvalidBitPattern>;
+//         pub fn set_small_prime(&mut self, value: SmallPrime);
+//     }
+//
+// Widths above 8 bits work the same way.
+
+use bitfield::*;
+
+#[derive(BitfieldSpecifier, Debug, PartialEq)]
+#[bits = 4]
+pub enum SmallPrime {
+    Two = 0b0010,
+    Three = 0b0011,
+    Five = 0b0101,
+    Seven = 0b0111,
+    Eleven = 0b1011,
+    Thirteen = 0b1101,
+}
+
+#[derive(BitfieldSpecifier, Debug, PartialEq)]
+#[bits = 12]
+pub enum Status {
+    Ok = 200,
+    NotFound = 404,
+    Teapot = 418,
+}
+
+#[bitfield]
+pub struct MyBitfield {
+    small_prime: SmallPrime,
+    status: Status,
+}
+
+fn main() {
+    assert_eq!(<SmallPrime as Specifier>::BITS, 4);
+    assert_eq!(<Status as Specifier>::BITS, 12);
+
+    let mut bitfield = MyBitfield::new();
+    assert_eq!(0, bitfield.get_small_prime().unwrap_err().raw_value());
+
+    bitfield.set_small_prime(SmallPrime::Seven);
+    let p = bitfield.get_small_prime().unwrap_or(SmallPrime::Two);
+    assert_eq!(p, SmallPrime::Seven);
+
+    bitfield.set_status(Status::Teapot);
+    assert_eq!(bitfield.get_status(), Ok(Status::Teapot));
+    assert_eq!(bitfield.get_small_prime(), Ok(SmallPrime::Seven));
+
+    let bitfield = MyBitfield::from_bytes([0xff, 0xff]);
+    assert_eq!(bitfield.get_small_prime().unwrap_err().raw_value(), 0b1111);
+    assert_eq!(bitfield.get_status().unwrap_err().raw_value(), 0xfff);
+}
@@ -0,0 +1,13 @@
+// With #[bits = N] every discriminant must still fit in N bits.
+
+use bitfield::*;
+
+#[derive(BitfieldSpecifier)]
+#[bits = 2]
+pub enum Mode {
+    Off = 0,
+    On = 1,
+    Auto = 4,
+}
+
+fn main() {}
@@ -0,0 +1,53 @@
+// A #[bitfield] struct is itself a Specifier whose BITS is the sum of its
+// fields' widths, so it can be used as a field of another #[bitfield] struct.
+// Getters and setters for such a field take and return the inner struct.
+//
+//     impl TcpHeader {
+//         pub fn get_flags(&self) -> Flags;
+//         pub fn set_flags(&mut self, value: Flags);
+//     }
+
+use bitfield::*;
+
+#[bitfield]
+pub struct Flags {
+    cwr: bool,
+    ece: bool,
+    urg: bool,
+    ack: bool,
+    psh: bool,
+    rst: bool,
+    syn: bool,
+    fin: bool,
+}
+
+#[bitfield]
+pub struct TcpHeader {
+    data_offset: B4,
+    reserved: B4,
+    flags: Flags,
+    window: B16,
+}
+
+fn main() {
+    assert_eq!(<Flags as Specifier>::BITS, 8);
+    assert_eq!(<TcpHeader as Specifier>::BITS, 32);
+    assert_eq!(std::mem::size_of::<TcpHeader>(), 4);
+
+    let mut flags = Flags::new();
+    flags.set_syn(true);
+    flags.set_ack(true);
+
+    let mut header = TcpHeader::new();
+    header.set_data_offset(5);
+    header.set_flags(flags);
+    header.set_window(0xffff);
+
+    let flags = header.get_flags();
+    assert!(flags.get_syn());
+    assert!(flags.get_ack());
+    assert!(!flags.get_fin());
+    assert_eq!(header.get_data_offset(), 5);
+    assert_eq!(header.get_window(), 0xffff);
+    assert_eq!(header.as_bytes()[1], flags.into_bytes()[0]);
+}
@@ -0,0 +1,56 @@
+// The attributes written on the struct are kept on the generated struct, and
+// so is its visibility. Derives are handled as follows:
+//
+//   - Debug prints the logical fields, not the bytes. It reads their bits
+//     directly, so fields with skipped or private getters are shown too.
+//   - Default is the same as new().
+//   - Everything else, such as Clone, Copy, PartialEq, Eq and Hash, is derived
+//     on the generated struct, where comparing or hashing the packed bytes is
+//     equivalent to doing so field by field, `#[skip]` padding included.
+
+mod registers {
+    use bitfield::*;
+
+    /// Interrupt redirection entry.
+    #[bitfield]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+    #[allow(dead_code)]
+    pub struct Entry {
+        pub vector: B8,
+        pub masked: bool,
+        pub mode: DeliveryMode,
+        reserved: B5,
+    }
+
+    #[derive(BitfieldSpecifier, Debug, PartialEq)]
+    pub enum DeliveryMode {
+        Fixed = 0b00,
+        Lowest = 0b01,
+        Smi = 0b10,
+        Nmi = 0b11,
+    }
+}
+
+use registers::{DeliveryMode, Entry};
+use std::collections::HashSet;
+
+fn main() {
+    let mut entry = Entry::default();
+    entry.set_vector(0x30);
+    entry.set_masked(true);
+    entry.set_mode(DeliveryMode::Nmi);
+
+    assert_eq!(
+        format!("{:?}", entry),
+        "Entry { vector: 48, masked: true, mode: Nmi, reserved: 0 }",
+    );
+
+    let copy = entry;
+    assert_eq!(copy, entry);
+    assert_ne!(copy, Entry::default());
+
+    let mut set = HashSet::new();
+    set.insert(entry);
+    set.insert(copy);
+    assert_eq!(set.len(), 1);
+}
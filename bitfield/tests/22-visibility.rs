@@ -0,0 +1,47 @@
+// The generated struct and its `new` keep the visibility written on the
+// struct, and each field's accessors take the visibility of the field.
+//
+// A field can give its accessors a different visibility with
+// `#[vis = "..."]`, and leave out its getters or setters altogether with
+// `#[skip(getters)]` or `#[skip(setters)]`, e.g. for reserved bits that
+// should not be part of the public interface.
+
+mod device {
+    use bitfield::*;
+
+    #[bitfield]
+    #[derive(Debug)]
+    pub struct Status {
+        pub ready: bool,
+        #[vis = "pub(crate)"]
+        code: B4,
+        #[skip(setters)]
+        pub revision: B3,
+        #[skip(getters, setters)]
+        reserved: B8,
+    }
+
+    impl Status {
+        pub fn reset() -> Self {
+            let mut status = Status::new();
+            status.set_code(0b1010);
+            status
+        }
+    }
+}
+
+use device::Status;
+
+fn main() {
+    let mut status = Status::reset();
+    status.set_ready(true);
+
+    assert!(status.get_ready());
+    assert_eq!(status.get_code(), 0b1010);
+    assert_eq!(status.get_revision(), 0);
+
+    assert_eq!(
+        format!("{:?}", status),
+        "Status { ready: true, code: 10, revision: 0, reserved: 0 }",
+    );
+}
@@ -0,0 +1,23 @@
+// Accessors follow the visibility of their field, so private fields do not
+// get accessors usable outside the struct's module, and skipped accessors are
+// not generated at all.
+
+mod device {
+    use bitfield::*;
+
+    #[bitfield]
+    pub struct Status {
+        pub ready: bool,
+        code: B4,
+        #[skip(setters)]
+        pub revision: B3,
+    }
+}
+
+use device::Status;
+
+fn main() {
+    let mut status = Status::new();
+    status.set_code(1);
+    status.set_revision(1);
+}
@@ -0,0 +1,53 @@
+// Every field with setters also gets a `with_*` method, which takes the
+// bitfield by value and returns it with the field set, so that a value can be
+// built in a single expression.
+//
+// For the specifiers provided by the bitfield crate, `B1` through `B128`,
+// `I2` through `I64` and `bool`, `with_*` is a `const fn` and can be used to
+// build constants.
+// Other specifiers go through trait methods, which cannot be called in a
+// `const fn`, so their `with_*` is an ordinary method.
+
+use bitfield::*;
+
+#[bitfield]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Header {
+    version: B4,
+    urgent: bool,
+    kind: Kind,
+    length: B10,
+    checksum: B16,
+}
+
+#[derive(BitfieldSpecifier, Debug, PartialEq)]
+pub enum Kind {
+    Data,
+    Ack,
+}
+
+const fn header(version: u8, urgent: bool) -> Header {
+    Header::from_bytes([0; 4]).with_version(version).with_urgent(urgent)
+}
+
+const V2: Header = header(2, true).with_length(300).with_checksum(0xbeef);
+
+fn main() {
+    assert_eq!(V2.get_version(), 2);
+    assert!(V2.get_urgent());
+    assert_eq!(V2.get_kind(), Kind::Data);
+    assert_eq!(V2.get_length(), 300);
+    assert_eq!(V2.get_checksum(), 0xbeef);
+
+    let ack = V2.with_kind(Kind::Ack).with_urgent(false);
+    assert_eq!(ack.get_kind(), Kind::Ack);
+    assert!(!ack.get_urgent());
+    assert_eq!(ack.get_length(), 300);
+
+    let mut set = Header::new();
+    set.set_version(2);
+    set.set_urgent(true);
+    set.set_length(300);
+    set.set_checksum(0xbeef);
+    assert_eq!(set, V2);
+}
@@ -0,0 +1,39 @@
+// `new` is a `const fn`, and so are the getters, setters and `with_*` methods
+// of fields using the specifiers provided by the bitfield crate, `B1` through
+// `B128`, `I2` through `I64` and `bool`. Register reset values and protocol
+// constants can then be declared as `const` items and inspected at compile
+// time.
+//
+// Accessors of other specifiers, such as enums or nested bitfields, go through
+// trait methods and are not `const`.
+
+use bitfield::*;
+
+#[bitfield]
+pub struct Control {
+    enable: bool,
+    prescaler: B7,
+    period: B24,
+}
+
+const RESET: Control = Control::new().with_prescaler(64).with_period(1_000_000);
+
+const ENABLED: Control = {
+    let mut control = RESET;
+    control.set_enable(true);
+    control
+};
+
+const _: () = assert!(RESET.get_period() == 1_000_000);
+const _: () = assert!(!RESET.get_enable() && ENABLED.get_enable());
+
+const OVERFLOW: bool = {
+    let mut control = Control::new();
+    control.set_prescaler_checked(128).is_err()
+};
+
+fn main() {
+    const PRESCALER: u8 = ENABLED.get_prescaler();
+    assert_eq!(PRESCALER, 64);
+    assert!(OVERFLOW);
+}
@@ -0,0 +1,79 @@
+// A field can choose the type taken and returned by its accessors with
+// `#[bitfield(repr = T)]`. The getter converts the specifier's output with
+// `T: From<Output>`, and the setters convert the given value into the
+// specifier's bucket with `Bucket: TryFrom<T>`. This covers both wider
+// integer types and user-defined newtypes.
+//
+// A value that fails to convert is out of bounds just like a value that does
+// not fit in the field: `set_*_checked` returns `OutOfBounds` and `set_*`
+// debug-asserts. In release builds `set_*` truncates a value that converts
+// but is too wide, and leaves the field unchanged for one that does not
+// convert.
+
+use bitfield::*;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Celsius(u8);
+
+impl From<u8> for Celsius {
+    fn from(raw: u8) -> Self {
+        Celsius(raw)
+    }
+}
+
+impl From<Celsius> for u8 {
+    fn from(celsius: Celsius) -> Self {
+        celsius.0
+    }
+}
+
+#[bitfield]
+pub struct Sensor {
+    #[bitfield(repr = u32)]
+    channel: B3,
+    #[bitfield(repr = Celsius)]
+    temperature: B7,
+    alarm: bool,
+    counter: B5,
+    #[bitfield(repr = u32)]
+    raw: B7,
+    padding: B1,
+}
+
+fn main() {
+    let mut sensor = Sensor::new();
+
+    sensor.set_channel(5u32);
+    let channel: u32 = sensor.get_channel();
+    assert_eq!(channel, 5);
+
+    sensor.set_temperature(Celsius(42));
+    assert_eq!(sensor.get_temperature(), Celsius(42));
+
+    let sensor = sensor.with_alarm(true).with_counter(31);
+    assert!(sensor.get_alarm());
+    assert_eq!(sensor.get_counter(), 31);
+
+    let mut sensor = sensor;
+    assert_eq!(sensor.set_channel_checked(8), Err(OutOfBounds));
+    assert_eq!(sensor.set_channel_checked(300), Err(OutOfBounds));
+    assert_eq!(sensor.get_channel(), 5);
+
+    sensor.set_raw(100);
+    assert_eq!(sensor.set_raw_checked(200), Err(OutOfBounds));
+    assert_eq!(sensor.set_raw_checked(300), Err(OutOfBounds));
+    assert_eq!(sensor.get_raw(), 100);
+
+    if cfg!(debug_assertions) {
+        // Fits the `u8` bucket, but not the 7 bits of the field.
+        let result = std::panic::catch_unwind(|| Sensor::new().with_raw(200));
+        assert!(result.is_err());
+
+        // Does not even fit the bucket.
+        let result = std::panic::catch_unwind(|| Sensor::new().with_raw(300));
+        assert!(result.is_err());
+    } else {
+        assert_eq!(Sensor::new().with_raw(200).get_raw(), 200 & 0x7f);
+        assert_eq!(Sensor::new().with_raw(300).get_raw(), 0);
+    }
+}
@@ -0,0 +1,48 @@
+// Specifiers I2 through I64 hold two's complement signed integers. Getters
+// sign-extend the stored bits into the smallest of i8, i16, i32 and i64 that
+// fits, and setters check that the value lies in the range representable with
+// the field's width, e.g. -8..=7 for I4.
+//
+// Like B1 through B64, their accessors are `const fn`s.
+
+use bitfield::*;
+
+#[bitfield]
+pub struct Reading {
+    offset: I4,
+    temperature: I12,
+    raw: B16,
+}
+
+const COLD: Reading = Reading::new().with_temperature(-273).with_offset(-1);
+
+fn main() {
+    assert_eq!(<I4 as Specifier>::BITS, 4);
+    assert_eq!(<I12 as Specifier>::BITS, 12);
+    assert_eq!(<I64 as Specifier>::BITS, 64);
+
+    let offset: i8 = COLD.get_offset();
+    let temperature: i16 = COLD.get_temperature();
+    assert_eq!(offset, -1);
+    assert_eq!(temperature, -273);
+    assert_eq!(COLD.get_raw(), 0);
+
+    let mut reading = COLD;
+    reading.set_offset(7);
+    reading.set_raw(0xffff);
+    assert_eq!(reading.get_offset(), 7);
+    assert_eq!(reading.get_temperature(), -273);
+
+    reading.set_offset(-8);
+    reading.set_temperature(2047);
+    assert_eq!(reading.get_offset(), -8);
+    assert_eq!(reading.get_temperature(), 2047);
+    assert_eq!(reading.get_raw(), 0xffff);
+
+    assert_eq!(reading.set_offset_checked(8), Err(OutOfBounds));
+    assert_eq!(reading.set_offset_checked(-9), Err(OutOfBounds));
+    assert_eq!(reading.set_temperature_checked(-2049), Err(OutOfBounds));
+    assert_eq!(reading.set_temperature_checked(-2048), Ok(()));
+    assert_eq!(reading.get_temperature(), -2048);
+    assert_eq!(reading.get_offset(), -8);
+}
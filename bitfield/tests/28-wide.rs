@@ -0,0 +1,58 @@
+// Specifiers B65 through B128 hold fields wider than 64 bits in a u128
+// bucket. Every specifier uses the smallest of u8, u16, u32, u64 and u128
+// that fits its width, so B32 has a u32 bucket.
+//
+// Wide fields work with every bit order and endianness, and bitfields of up
+// to 128 bits can be nested in other bitfields.
+
+use bitfield::*;
+
+#[bitfield]
+#[derive(Clone, Copy)]
+pub struct Address {
+    flags: B4,
+    address: B112,
+    scope: B4,
+}
+
+#[bitfield(bits = "msb0", endian = "little")]
+pub struct Wide {
+    id: B32,
+    huge: B65,
+    rest: B7,
+}
+
+#[bitfield]
+pub struct Route {
+    valid: bool,
+    next_hop: Address,
+    metric: B7,
+}
+
+fn main() {
+    let id: u32 = Wide::new().get_id();
+    assert_eq!(id, 0);
+
+    let huge: u128 = (1 << 64) | 0x0123_4567_89ab_cdef;
+    let wide = Wide::new().with_id(u32::MAX).with_huge(huge).with_rest(0x55);
+    assert_eq!(wide.get_id(), u32::MAX);
+    assert_eq!(wide.get_huge(), huge);
+    assert_eq!(wide.get_rest(), 0x55);
+
+    let mut address = Address::new();
+    address.set_flags(0b1001);
+    address.set_address((1 << 111) | 0xdead_beef);
+    address.set_scope(0b0110);
+    assert_eq!(address.get_flags(), 0b1001);
+    assert_eq!(address.get_address(), (1 << 111) | 0xdead_beef);
+    assert_eq!(address.get_scope(), 0b0110);
+
+    let mut address_checked = Address::new();
+    assert_eq!(address_checked.set_address_checked(1 << 112), Err(OutOfBounds));
+    assert_eq!(address_checked.get_address(), 0);
+
+    let route = Route::new().with_valid(true).with_next_hop(address).with_metric(100);
+    assert!(route.get_valid());
+    assert_eq!(route.get_next_hop().as_bytes(), address.as_bytes());
+    assert_eq!(route.get_metric(), 100);
+}
@@ -0,0 +1,58 @@
+// A field can be an array of specifiers, such as eight 4-bit priorities
+// declared as `prio: [B4; 8]`. The elements are laid out one after another and
+// the whole array counts towards the size of the struct.
+//
+// Array fields get indexed accessors `get_prio(i)`, `set_prio(i, v)`,
+// `set_prio_checked(i, v)` and `with_prio(i, v)`, which panic when the index
+// is out of range like slice indexing does, and an `iter_prio()` iterator over
+// all of the elements in order.
+
+use bitfield::*;
+use std::mem::size_of;
+use std::panic::catch_unwind;
+
+#[bitfield]
+#[derive(Debug, Clone, Copy)]
+pub struct Descriptor {
+    id: B4,
+    #[bits = 32]
+    prio: [B4; 8],
+    enabled: [bool; 4],
+}
+
+const DEFAULT: Descriptor = Descriptor::new().with_prio(0, 15).with_prio(7, 1);
+
+fn main() {
+    assert_eq!(size_of::<Descriptor>(), 5);
+
+    assert_eq!(DEFAULT.get_prio(0), 15);
+    assert_eq!(DEFAULT.get_prio(7), 1);
+
+    let mut descriptor = DEFAULT;
+    descriptor.set_id(9);
+    for i in 0..8 {
+        descriptor.set_prio(i, i as u8 * 2);
+    }
+    descriptor.set_enabled(2, true);
+
+    assert_eq!(descriptor.get_id(), 9);
+    assert_eq!(
+        descriptor.iter_prio().collect::<Vec<_>>(),
+        [0, 2, 4, 6, 8, 10, 12, 14],
+    );
+    assert_eq!(
+        descriptor.iter_enabled().collect::<Vec<_>>(),
+        [false, false, true, false],
+    );
+
+    assert_eq!(descriptor.set_prio_checked(3, 16), Err(OutOfBounds));
+    assert_eq!(descriptor.get_prio(3), 6);
+
+    assert_eq!(
+        format!("{:?}", descriptor),
+        "Descriptor { id: 9, prio: [0, 2, 4, 6, 8, 10, 12, 14], \
+         enabled: [false, false, true, false] }",
+    );
+
+    assert!(catch_unwind(|| DEFAULT.get_prio(8)).is_err());
+}
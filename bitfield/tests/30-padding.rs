@@ -0,0 +1,47 @@
+// Hardware registers often have reserved bits. A field marked `#[skip]` takes
+// up its bits in the layout but gets no accessors and does not show up in the
+// `Debug` output. Since the name of such a field is never used, several of
+// them may share a placeholder name like `__`. (A bare `_` cannot be used: the
+// compiler rejects it as a field name before the attribute macro runs.)
+//
+// `#[reserved(value = ...)]` additionally makes `new` store the given bits in
+// the field, for reserved bits that must be written as a particular pattern.
+// The setters of other fields leave those bits untouched.
+//
+// Padding is still part of the stored bits, which are preserved as they are
+// read, so derived `PartialEq` and `Hash` take it into account even though
+// `Debug` does not show it.
+
+use bitfield::*;
+
+#[bitfield]
+#[derive(Debug, Default, PartialEq)]
+pub struct Config {
+    enable: bool,
+    #[skip]
+    __: B3,
+    mode: B4,
+    #[reserved(value = 0b101)]
+    __: B3,
+    #[skip]
+    __: B5,
+}
+
+fn main() {
+    let mut config = Config::new();
+    assert_eq!(config.as_bytes(), &[0b0000_0000, 0b0000_0101]);
+
+    config.set_enable(true);
+    config.set_mode(0b1111);
+    assert_eq!(config.get_mode(), 0b1111);
+    assert_eq!(config.as_bytes(), &[0b1111_0001, 0b0000_0101]);
+
+    let default = Config::default();
+    assert_eq!(default.as_bytes(), Config::new().as_bytes());
+
+    assert_eq!(format!("{:?}", config), "Config { enable: true, mode: 15 }");
+
+    let padded = Config::from_bytes([0b0000_0000, 0b1000_0000]);
+    assert_eq!(format!("{:?}", padded), "Config { enable: false, mode: 0 }");
+    assert_ne!(padded, Config::from_bytes([0; 2]));
+}
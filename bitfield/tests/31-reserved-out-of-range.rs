@@ -0,0 +1,13 @@
+// The value of a reserved field has to fit in the field.
+
+use bitfield::*;
+
+#[bitfield]
+pub struct Config {
+    enable: bool,
+    #[reserved(value = 0b1000)]
+    reserved: B3,
+    mode: B4,
+}
+
+fn main() {}
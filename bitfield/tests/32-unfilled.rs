@@ -0,0 +1,54 @@
+// By default the fields of a bitfield have to add up to a multiple of 8 bits,
+// see 04-multiple-of-8bits. For in-memory flag sets where the exact storage
+// does not matter, `#[bitfield(filled = false)]` lifts that restriction: the
+// storage is rounded up to the next whole byte and the leftover bits are left
+// unused. `from_bytes` and `TryFrom<&[u8]>` clear them, so that derived
+// `PartialEq` and `Hash` only see the fields.
+//
+// The bitfield still counts as exactly as many bits as its fields when it is
+// nested in another bitfield.
+
+use bitfield::*;
+use std::collections::HashSet;
+use std::mem::size_of;
+
+#[bitfield(filled = false)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Flags {
+    read: bool,
+    write: bool,
+    execute: bool,
+    owner: B9,
+}
+
+#[bitfield]
+pub struct Entry {
+    flags: Flags,
+    group: B4,
+}
+
+#[bitfield(filled = false)]
+pub struct Empty {}
+
+fn main() {
+    assert_eq!(size_of::<Flags>(), 2);
+    assert_eq!(<Flags as Specifier>::BITS, 12);
+    assert_eq!(size_of::<Empty>(), 0);
+
+    let flags = Flags::new().with_read(true).with_execute(true).with_owner(511);
+    assert!(flags.get_read());
+    assert!(!flags.get_write());
+    assert!(flags.get_execute());
+    assert_eq!(flags.get_owner(), 511);
+
+    let clean = Flags::from_bytes([0b0000_0101, 0b0000_0000]);
+    let dirty = Flags::from_bytes([0b0000_0101, 0b1111_0000]);
+    assert_eq!(clean, dirty);
+    assert_eq!(dirty.into_bytes(), [0b0000_0101, 0b0000_0000]);
+    assert_eq!(HashSet::from([clean, dirty]).len(), 1);
+
+    let entry = Entry::new().with_flags(flags).with_group(0b1010);
+    assert_eq!(size_of::<Entry>(), 2);
+    assert_eq!(entry.get_flags().get_owner(), 511);
+    assert_eq!(entry.get_group(), 0b1010);
+}
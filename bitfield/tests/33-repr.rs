@@ -0,0 +1,63 @@
+// `#[bitfield(repr = u32)]` stores the struct as a `u32` instead of a byte
+// array, with `#[repr(transparent)]`, so that it can be read and written in a
+// single access, e.g. with volatile reads and writes to a memory-mapped
+// register. The integer types u8, u16, u32, u64 and u128 are supported.
+//
+// Bit 0 is the least significant bit of the integer with `bits = "lsb0"`, and
+// the most significant one with `bits = "msb0"`. Combined with
+// `endian = "little"`, a field at bit `n` then holds `(register >> n) & mask`.
+//
+// Conversions from and to the integer are provided through `From`. Since the
+// bytes are not stored as such, there is no `as_bytes`, but `from_bytes` and
+// `into_bytes` still work.
+
+use bitfield::*;
+use std::mem::{align_of, size_of};
+use std::ptr;
+
+#[bitfield(repr = u32, endian = "little")]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Control {
+    enable: bool,
+    mode: B3,
+    prescaler: B12,
+    reload: B16,
+}
+
+#[bitfield(repr = u16, endian = "little", filled = false)]
+pub struct Status {
+    ready: bool,
+    errors: B11,
+}
+
+fn main() {
+    assert_eq!(size_of::<Control>(), 4);
+    assert_eq!(align_of::<Control>(), 4);
+
+    let control = Control::new()
+        .with_enable(true)
+        .with_mode(0b101)
+        .with_prescaler(0xabc)
+        .with_reload(0x1234);
+
+    let raw: u32 = control.into();
+    assert_eq!(raw, 0x1234_abcb);
+    assert_eq!(Control::from(raw), control);
+    assert_eq!(control.into_bytes(), raw.to_le_bytes());
+    assert_eq!(Control::from_bytes(raw.to_le_bytes()), control);
+
+    let mut register = 0u32;
+    unsafe { ptr::write_volatile(&mut register as *mut u32 as *mut Control, control) };
+    assert_eq!(register, 0x1234_abcb);
+
+    let read = unsafe { ptr::read_volatile(&register as *const u32 as *const Control) };
+    assert_eq!(read.get_prescaler(), 0xabc);
+
+    let status = Status::from(0b0000_0000_0101_0001);
+    assert_eq!(size_of::<Status>(), 2);
+    assert!(status.get_ready());
+    assert_eq!(status.get_errors(), 0b10_1000);
+
+    // The four bits past `errors` are unused and cleared on the way in.
+    assert_eq!(u16::from(Status::from(0xffff)), 0x0fff);
+}
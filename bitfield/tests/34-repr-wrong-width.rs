@@ -0,0 +1,19 @@
+// With `repr = u32` the fields have to add up to exactly 32 bits, unless
+// `filled = false` is given too, in which case they can use fewer.
+
+use bitfield::*;
+
+#[bitfield(repr = u32)]
+pub struct Control {
+    enable: bool,
+    mode: B3,
+    prescaler: B12,
+}
+
+#[bitfield(repr = u8, filled = false)]
+pub struct Status {
+    ready: bool,
+    errors: B8,
+}
+
+fn main() {}
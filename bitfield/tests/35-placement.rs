@@ -0,0 +1,38 @@
+// By default each field starts where the previous one ends. Datasheets give
+// explicit bit positions instead, so a field can be placed with
+// `#[bits(4..=7)]` (or `#[bits(4..8)]`), whose width has to match the
+// specifier, or with `#[at(4)]`, which only gives the starting bit. Fields
+// without either keep following the previous field, so they can be declared
+// in any order.
+//
+// The fields still have to cover every bit exactly once, see
+// 36-placement-wrong.
+
+use bitfield::*;
+
+#[bitfield(repr = u16, endian = "little")]
+#[derive(Debug)]
+pub struct Register {
+    #[bits(8..=15)]
+    high: B8,
+    #[at(0)]
+    enable: bool,
+    mode: B3,
+    #[bits(4..8)]
+    prio: B4,
+}
+
+fn main() {
+    let register = Register::new()
+        .with_high(0xab)
+        .with_enable(true)
+        .with_mode(0b101)
+        .with_prio(0x3);
+
+    assert_eq!(u16::from(register), 0xab3b);
+    assert_eq!(
+        format!("{:?}", Register::from(0xab3b)),
+        "Register { high: 171, enable: true, mode: 5, prio: 3 }",
+    );
+    assert_eq!(<Register as Specifier>::BITS, 16);
+}
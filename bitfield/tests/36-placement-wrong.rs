@@ -0,0 +1,31 @@
+// Explicitly placed fields are checked at compile time: a `#[bits(..)]` range
+// has to match the width of the field's type, no two fields may overlap, and
+// there may be no gaps between them. Gaps can be filled with `#[skip]` fields.
+
+use bitfield::*;
+
+#[bitfield]
+pub struct WrongWidth {
+    #[bits(0..=3)]
+    a: B3,
+    b: B5,
+}
+
+#[bitfield]
+pub struct Overlap {
+    #[bits(0..=3)]
+    a: B4,
+    #[at(2)]
+    b: B6,
+    c: B8,
+}
+
+#[bitfield]
+pub struct Gap {
+    #[at(0)]
+    a: B4,
+    #[at(8)]
+    b: B8,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+// Like 11-bits-attribute-wrong, for an array field: the error points at the
+// `#[bits = N]` attribute and states the width of the whole array.
+
+use bitfield::*;
+
+#[bitfield]
+pub struct Priorities {
+    #[bits = 24]
+    prio: [B4; 8],
+}
+
+fn main() {}
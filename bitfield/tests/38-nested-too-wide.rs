@@ -0,0 +1,21 @@
+// Field values are passed around as u128, so a field cannot be wider than 128
+// bits. Nesting a wider bitfield is rejected at compile time, as is any other
+// specifier with BITS above 128.
+
+use bitfield::*;
+
+#[bitfield]
+pub struct Big {
+    a: B64,
+    b: B64,
+    c: B8,
+}
+
+#[bitfield]
+pub struct Outer {
+    flag: bool,
+    big: Big,
+    rest: B7,
+}
+
+fn main() {}
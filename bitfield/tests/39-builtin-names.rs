@@ -0,0 +1,54 @@
+// Only `bool` and the specifiers of this crate get the built-in `const`
+// accessors, which cast the raw bits. A user specifier that happens to be
+// called `B2` is named through its own module, and its conversions are used.
+
+use bitfield::*;
+
+mod regs {
+    use bitfield::Specifier;
+
+    // A 2-bit counter stored as Gray code.
+    pub enum B2 {}
+
+    impl Specifier for B2 {
+        type Bucket = u8;
+        type Output = u8;
+        const BITS: usize = 2;
+
+        fn into_bits(bucket: u8) -> u128 {
+            (bucket ^ (bucket >> 1)) as u128
+        }
+
+        fn from_bits(bits: u128) -> u8 {
+            let bits = bits as u8;
+            bits ^ (bits >> 1)
+        }
+    }
+}
+
+#[bitfield]
+pub struct Counter {
+    count: regs::B2,
+    plain: B2,
+    rest: B4,
+}
+
+#[bitfield]
+pub struct Raw {
+    count: B2,
+    plain: B2,
+    rest: B4,
+}
+
+fn main() {
+    let mut counter = Counter::new();
+
+    counter.set_count(2);
+    counter.set_plain(2);
+    assert_eq!(counter.get_count(), 2);
+    assert_eq!(counter.get_plain(), 2);
+
+    let raw = Raw::from_bytes(counter.into_bytes());
+    assert_eq!(raw.get_count(), 0b11);
+    assert_eq!(raw.get_plain(), 0b10);
+}
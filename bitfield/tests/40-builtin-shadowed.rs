@@ -0,0 +1,39 @@
+// The built-in specifiers get `const` accessors that cast the raw bits, and
+// are recognized by name. A user specifier imported under one of those names
+// would silently lose its own conversions, so that is an error instead.
+
+use bitfield::{bitfield, Specifier};
+
+mod regs {
+    use bitfield::Specifier;
+
+    // A 2-bit counter stored as Gray code.
+    pub enum B2 {}
+
+    impl Specifier for B2 {
+        type Bucket = u8;
+        type Output = u8;
+        const BITS: usize = 2;
+
+        fn into_bits(bucket: u8) -> u128 {
+            (bucket ^ (bucket >> 1)) as u128
+        }
+
+        fn from_bits(bits: u128) -> u8 {
+            let bits = bits as u8;
+            bits ^ (bits >> 1)
+        }
+    }
+}
+
+use regs::B2;
+
+#[bitfield]
+pub struct Counter {
+    count: B2,
+    rest: bitfield::B6,
+}
+
+fn main() {
+    assert_eq!(<B2 as Specifier>::BITS, 2);
+}
@@ -13,4 +13,32 @@ fn tests() {
     t.pass("tests/10-bits-attribute.rs");
     t.compile_fail("tests/11-bits-attribute-wrong.rs");
     t.pass("tests/12-accessors-edge.rs");
+    t.pass("tests/13-bit-order.rs");
+    t.compile_fail("tests/14-bit-order-wrong.rs");
+    t.pass("tests/15-bytes.rs");
+    t.pass("tests/16-checked-setters.rs");
+    t.pass("tests/17-wide-enums.rs");
+    t.pass("tests/18-bits-enum.rs");
+    t.compile_fail("tests/19-bits-enum-out-of-range.rs");
+    t.pass("tests/20-nested.rs");
+    t.pass("tests/21-derives.rs");
+    t.pass("tests/22-visibility.rs");
+    t.compile_fail("tests/23-visibility-private.rs");
+    t.pass("tests/24-with.rs");
+    t.pass("tests/25-const.rs");
+    t.pass("tests/26-field-repr.rs");
+    t.pass("tests/27-signed.rs");
+    t.pass("tests/28-wide.rs");
+    t.pass("tests/29-arrays.rs");
+    t.pass("tests/30-padding.rs");
+    t.compile_fail("tests/31-reserved-out-of-range.rs");
+    t.pass("tests/32-unfilled.rs");
+    t.pass("tests/33-repr.rs");
+    t.compile_fail("tests/34-repr-wrong-width.rs");
+    t.pass("tests/35-placement.rs");
+    t.compile_fail("tests/36-placement-wrong.rs");
+    t.compile_fail("tests/37-bits-attribute-array-wrong.rs");
+    t.compile_fail("tests/38-nested-too-wide.rs");
+    t.pass("tests/39-builtin-names.rs");
+    t.compile_fail("tests/40-builtin-shadowed.rs");
 }
@@ -0,0 +1,62 @@
+// Crates that have the "proc-macro" crate type are only allowed to export
+// procedural macros, so the traits implemented by `#[derive(Builder)]` live in
+// this crate instead. Generated code refers to them through absolute
+// `::builder_runtime` paths.
+use std::fmt;
+
+/// Implemented for every struct deriving `Builder`.
+pub trait Buildable: Sized {
+    type Builder: Builder<Target = Self>;
+
+    fn builder() -> Self::Builder;
+}
+
+/// Implemented for every builder generated by `#[derive(Builder)]`.
+pub trait Builder {
+    type Target;
+    type Error;
+
+    fn build(&self) -> Result<Self::Target, Self::Error>;
+}
+
+/// Returned by `build()` when a required field was never set.
+///
+/// Errors coming out of a nested sub-builder keep the path of fields leading
+/// to them, e.g. `server.port`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UninitializedField {
+    path: Vec<&'static str>,
+}
+
+impl UninitializedField {
+    pub fn new(field: &'static str) -> UninitializedField {
+        let path = vec![field];
+
+        UninitializedField { path }
+    }
+
+    /// Prefixes the path with the field holding the sub-builder that failed.
+    pub fn nested(mut self, parent: &'static str) -> UninitializedField {
+        self.path.insert(0, parent);
+
+        self
+    }
+
+    /// The innermost field that was never set.
+    pub fn field(&self) -> &'static str {
+        self.path[self.path.len() - 1]
+    }
+
+    /// The dotted path from the outermost builder to the missing field.
+    pub fn path(&self) -> String {
+        self.path.join(".")
+    }
+}
+
+impl fmt::Display for UninitializedField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}` was never set", self.path())
+    }
+}
+
+impl std::error::Error for UninitializedField {}
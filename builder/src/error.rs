@@ -2,24 +2,21 @@ use proc_macro2::Span;
 
 #[derive(Debug)]
 pub(crate) enum Error {
-    IdentNeverSpecified,
     NeedNamedParams,
-    PhaseMismatch,
-    ParseError(syn::parse::Error),
+    NeedStruct,
+    Parse(syn::parse::Error),
 }
 
-impl Into<proc_macro::TokenStream> for Error {
-    fn into(self) -> proc_macro::TokenStream {
-        let parse_error = match self {
-            Error::IdentNeverSpecified => {
-                panic!()
-            }
-
-            Error::PhaseMismatch => syn::parse::Error::new(Span::call_site(), "Phase mismatch"),
+impl From<Error> for proc_macro::TokenStream {
+    fn from(error: Error) -> proc_macro::TokenStream {
+        let parse_error = match error {
             Error::NeedNamedParams => {
                 syn::parse::Error::new(Span::call_site(), "Need named fields to generate setters")
             }
-            Error::ParseError(e) => e,
+            Error::NeedStruct => {
+                syn::parse::Error::new(Span::call_site(), "Builder can only be derived for structs")
+            }
+            Error::Parse(e) => e,
         };
 
         parse_error.to_compile_error().into()
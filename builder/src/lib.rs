@@ -1,7 +1,5 @@
 use syn::{parse_macro_input, visit::Visit, DeriveInput};
 
-use error::Error;
-
 mod error;
 mod state;
 mod visit;
@@ -10,7 +8,7 @@ mod visit;
 pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
-    let mut builder = Builder::default();
+    let mut builder = Builder::new(&input);
 
     builder.visit_derive_input(&input);
 
@@ -22,17 +20,17 @@ struct Builder {
     state: Result<state::State, error::Error>,
 }
 
-impl Default for Builder {
-    fn default() -> Self {
-        let state = Err(Error::IdentNeverSpecified);
+impl Builder {
+    fn new(input: &DeriveInput) -> Self {
+        let state = Ok(state::State::new(input.ident.clone()));
 
         Builder { state }
     }
 }
 
-impl Into<proc_macro::TokenStream> for Builder {
-    fn into(self) -> proc_macro::TokenStream {
-        match self.state {
+impl From<Builder> for proc_macro::TokenStream {
+    fn from(builder: Builder) -> proc_macro::TokenStream {
+        match builder.state {
             Ok(state) => state.gen().into(),
             Err(e) => e.into(),
         }
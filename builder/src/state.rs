@@ -6,6 +6,7 @@ mod gen;
 pub(crate) struct State {
     pub(crate) name: Ident,
     pub(crate) methods: Vec<Method>,
+    pub(crate) constant: bool,
 }
 
 #[derive(Debug)]
@@ -20,12 +21,18 @@ pub(crate) enum Mode {
     Single,
     Optional,
     Sub(Option<String>),
+    Nested,
 }
 
 impl State {
     pub(crate) fn new(name: Ident) -> State {
         let methods = Vec::new();
+        let constant = false;
 
-        State { name, methods }
+        State {
+            name,
+            methods,
+            constant,
+        }
     }
 }
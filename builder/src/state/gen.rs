@@ -5,6 +5,10 @@ use super::{Method, Mode, State};
 
 impl State {
     pub(crate) fn gen(&self) -> TokenStream {
+        if self.constant {
+            return self.gen_const();
+        }
+
         let builder_ident = format_ident!("{}Builder", self.name);
         let ident = format_ident!("{}", self.name);
 
@@ -13,6 +17,9 @@ impl State {
         let inits = self.methods.iter().map(Method::inits);
         let names = self.methods.iter().map(Method::name).collect::<Vec<_>>();
         let unwraps = self.methods.iter().map(Method::unwrap);
+        let clears = self.methods.iter().map(Method::clear);
+        let is_sets = self.methods.iter().map(Method::is_set);
+        let missing = self.methods.iter().map(Method::missing);
 
         quote! {
             pub struct #builder_ident {
@@ -22,10 +29,22 @@ impl State {
             impl #builder_ident {
                 #(#setters)*
 
-                pub fn build(&self) -> ::std::option::Option<#ident> {
+                #(#clears)*
+
+                #(#is_sets)*
+
+                pub fn missing_fields(&self) -> ::std::vec::Vec<&'static str> {
+                    let mut missing = ::std::vec::Vec::new();
+
+                    #(#missing)*
+
+                    missing
+                }
+
+                pub fn build(&self) -> ::std::result::Result<#ident, ::builder_runtime::UninitializedField> {
                     #(#unwraps)*
 
-                    Some(#ident { #(#names),* })
+                    ::std::result::Result::Ok(#ident { #(#names),* })
                 }
             }
 
@@ -36,6 +55,96 @@ impl State {
                     #builder_ident { #(#names),* }
                 }
             }
+
+            impl ::builder_runtime::Buildable for #ident {
+                type Builder = #builder_ident;
+
+                fn builder() -> #builder_ident {
+                    #ident::builder()
+                }
+            }
+
+            impl ::builder_runtime::Builder for #builder_ident {
+                type Target = #ident;
+                type Error = ::builder_runtime::UninitializedField;
+
+                fn build(&self) -> ::std::result::Result<#ident, ::builder_runtime::UninitializedField> {
+                    #builder_ident::build(self)
+                }
+            }
+        }
+    }
+
+    /// `builder(const)` builders are `Copy` and consumed by value, so that
+    /// every setter and `build` can be a `const fn`.
+    fn gen_const(&self) -> TokenStream {
+        let builder_ident = format_ident!("{}Builder", self.name);
+        let ident = format_ident!("{}", self.name);
+
+        let fields = self.methods.iter().map(Method::field);
+        let setters = self.methods.iter().map(Method::const_setter);
+        let inits = self.methods.iter().map(Method::inits);
+        let names = self.methods.iter().map(Method::name).collect::<Vec<_>>();
+        let const_unwraps = self.methods.iter().map(Method::const_unwrap);
+        let unwraps = self.methods.iter().map(Method::unwrap);
+        let clears = self.methods.iter().map(Method::const_clear);
+        let is_sets = self.methods.iter().map(Method::const_is_set);
+        let missing = self.methods.iter().map(Method::missing);
+
+        quote! {
+            #[derive(::std::clone::Clone, ::std::marker::Copy)]
+            pub struct #builder_ident {
+                #(#fields),*
+            }
+
+            impl #builder_ident {
+                #(#setters)*
+
+                #(#clears)*
+
+                #(#is_sets)*
+
+                pub fn missing_fields(&self) -> ::std::vec::Vec<&'static str> {
+                    let mut missing = ::std::vec::Vec::new();
+
+                    #(#missing)*
+
+                    missing
+                }
+
+                pub const fn build(self) -> #ident {
+                    #(#const_unwraps)*
+
+                    #ident { #(#names),* }
+                }
+            }
+
+            impl #ident {
+                pub const fn builder() -> #builder_ident {
+                    #(#inits);*
+
+                    #builder_ident { #(#names),* }
+                }
+            }
+
+            impl ::builder_runtime::Buildable for #ident {
+                type Builder = #builder_ident;
+
+                fn builder() -> #builder_ident {
+                    #ident::builder()
+                }
+            }
+
+            impl ::builder_runtime::Builder for #builder_ident {
+                type Target = #ident;
+                type Error = ::builder_runtime::UninitializedField;
+
+                fn build(&self) -> ::std::result::Result<#ident, ::builder_runtime::UninitializedField> {
+                    #(#unwraps)*
+
+                    ::std::result::Result::Ok(#ident { #(#names),* })
+                }
+            }
         }
     }
 }
@@ -50,6 +159,9 @@ impl Method {
             }
             Mode::Sub(None) => quote! { #name: ::std::option::Option<Vec<#ty>> },
             Mode::Sub(_) => quote! { #name: ::std::vec::Vec<#ty> },
+            Mode::Nested => {
+                quote! { #name: <#ty as ::builder_runtime::Buildable>::Builder }
+            }
         }
     }
 
@@ -68,6 +180,14 @@ impl Method {
                 }
             }
 
+            Mode::Nested => {
+                quote! {
+                    pub fn #name(&mut self) -> &mut <#ty as ::builder_runtime::Buildable>::Builder {
+                        &mut self.#name
+                    }
+                }
+            }
+
             Mode::Sub(None) => {
                 quote! {
                     pub fn #name(&mut self, #name: ::std::vec::Vec<#ty>) -> &mut Self {
@@ -107,13 +227,118 @@ impl Method {
         }
     }
 
+    fn const_setter(&self) -> TokenStream {
+        let name = &self.name;
+        let ty = &self.ty;
+
+        quote! {
+            pub const fn #name(mut self, #name: #ty) -> Self {
+                self.#name = ::std::option::Option::Some(#name);
+
+                self
+            }
+        }
+    }
+
     fn inits(&self) -> TokenStream {
         let name = format_ident!("{}", &self.name);
+        let ty = &self.ty;
         match self.mode {
             Mode::Single | Mode::Optional | Mode::Sub(None) => {
                 quote! { let #name = ::std::option::Option::None; }
             }
             Mode::Sub(_) => quote! { let #name = ::std::vec::Vec::new(); },
+            Mode::Nested => {
+                quote! { let #name = <#ty as ::builder_runtime::Buildable>::builder(); }
+            }
+        }
+    }
+
+    fn clear(&self) -> TokenStream {
+        let name = &self.name;
+        let ty = &self.ty;
+        let method = format_ident!("clear_{}", name);
+
+        let reset = match self.mode {
+            Mode::Single | Mode::Optional | Mode::Sub(None) => {
+                quote! { self.#name = ::std::option::Option::None; }
+            }
+            Mode::Sub(Some(_)) => quote! { self.#name.clear(); },
+            Mode::Nested => {
+                quote! { self.#name = <#ty as ::builder_runtime::Buildable>::builder(); }
+            }
+        };
+
+        quote! {
+            pub fn #method(&mut self) -> &mut Self {
+                #reset
+
+                self
+            }
+        }
+    }
+
+    fn const_clear(&self) -> TokenStream {
+        let name = &self.name;
+        let method = format_ident!("clear_{}", name);
+
+        quote! {
+            pub const fn #method(mut self) -> Self {
+                self.#name = ::std::option::Option::None;
+
+                self
+            }
+        }
+    }
+
+    fn is_set(&self) -> TokenStream {
+        let name = &self.name;
+        let method = format_ident!("{}_is_set", name);
+
+        let check = match self.mode {
+            Mode::Single | Mode::Optional | Mode::Sub(None) => quote! { self.#name.is_some() },
+            Mode::Sub(Some(_)) => quote! { !self.#name.is_empty() },
+            Mode::Nested => quote! { ::builder_runtime::Builder::build(&self.#name).is_ok() },
+        };
+
+        quote! {
+            pub fn #method(&self) -> bool {
+                #check
+            }
+        }
+    }
+
+    fn const_is_set(&self) -> TokenStream {
+        let name = &self.name;
+        let method = format_ident!("{}_is_set", name);
+
+        quote! {
+            pub const fn #method(&self) -> bool {
+                self.#name.is_some()
+            }
+        }
+    }
+
+    fn missing(&self) -> TokenStream {
+        let name = &self.name;
+        let field = name.to_string();
+
+        match self.mode {
+            Mode::Single | Mode::Sub(None) => {
+                quote! {
+                    if self.#name.is_none() {
+                        missing.push(#field);
+                    }
+                }
+            }
+            Mode::Nested => {
+                quote! {
+                    if ::builder_runtime::Builder::build(&self.#name).is_err() {
+                        missing.push(#field);
+                    }
+                }
+            }
+            Mode::Optional | Mode::Sub(Some(_)) => quote! {},
         }
     }
 
@@ -127,10 +352,40 @@ impl Method {
 
         match self.mode {
             Mode::Single | Mode::Sub(None) => {
-                quote! { let #name = self.#name.as_ref()?.clone(); }
+                let field = name.to_string();
+                quote! {
+                    let #name = self.#name
+                        .as_ref()
+                        .ok_or_else(|| ::builder_runtime::UninitializedField::new(#field))?
+                        .clone();
+                }
             }
             Mode::Optional => quote! { let #name = self.#name.clone(); },
             Mode::Sub(Some(_)) => quote! { let #name = self.#name.clone(); },
+            Mode::Nested => {
+                let field = name.to_string();
+                quote! {
+                    let #name = ::builder_runtime::Builder::build(&self.#name)
+                        .map_err(|e| e.nested(#field))?;
+                }
+            }
+        }
+    }
+
+    fn const_unwrap(&self) -> TokenStream {
+        let name = &self.name;
+
+        match self.mode {
+            Mode::Optional => quote! { let #name = self.#name; },
+            _ => {
+                let msg = format!("`{}` was never set", name);
+                quote! {
+                    let #name = match self.#name {
+                        ::std::option::Option::Some(#name) => #name,
+                        ::std::option::Option::None => ::std::panic!(#msg),
+                    };
+                }
+            }
         }
     }
 }
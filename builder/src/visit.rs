@@ -6,15 +6,26 @@ use syn::{
 
 use crate::{
     error::Error,
-    state::{Method, Mode, State},
+    state::{Method, Mode},
     Builder,
 };
 
 impl<'ast> Visit<'ast> for Builder {
     fn visit_derive_input(&mut self, i: &'ast DeriveInput) {
-        match self.state {
-            Err(Error::IdentNeverSpecified) => self.state = Ok(State::new(i.ident.clone())),
-            _ => (),
+        if !matches!(i.data, syn::Data::Struct(_)) {
+            self.state = Err(Error::NeedStruct);
+            return;
+        }
+
+        if let Ok(state) = &mut self.state {
+            state.constant = match extract_const_attr(&i.attrs) {
+                Ok(constant) => constant,
+                Err(e) => {
+                    self.state = Err(Error::Parse(e));
+
+                    return;
+                }
+            };
         }
 
         visit_derive_input(self, i);
@@ -26,59 +37,85 @@ impl<'ast> Visit<'ast> for Builder {
             return;
         };
 
-        match &mut self.state {
-            Ok(state) => {
-                if let Some(ty) = extract_vec(&i.ty) {
-                    let name = match extract_each_attr(&i.attrs) {
-                        Ok(name) => name,
-                        Err(e) => {
-                            self.state = Err(Error::ParseError(e));
-
-                            return;
-                        }
-                    };
-
-                    if let Some(name) = name {
-                        state.methods.push(Method {
-                            name: ident.clone(),
-                            ty: ty.clone(),
-                            mode: Mode::Sub(Some(name)),
-                        });
-                    } else {
-                        state.methods.push(Method {
-                            name: ident.clone(),
-                            ty: ty.clone(),
-                            mode: Mode::Sub(None),
-                        });
-                    }
-                } else if let Some(ty) = extract_option(&i.ty) {
-                    state.methods.push(Method {
-                        name: ident.clone(),
-                        ty: ty.clone(),
-                        mode: Mode::Optional,
-                    });
-                } else {
-                    state.methods.push(Method {
-                        name: ident.clone(),
-                        ty: i.ty.clone(),
-                        mode: Mode::Single,
-                    });
+        if let Ok(state) = &mut self.state {
+            let method = match extract_method(ident, i) {
+                Ok(method) => method,
+                Err(e) => {
+                    self.state = Err(Error::Parse(e));
+
+                    return;
                 }
+            };
+
+            if state.constant && matches!(method.mode, Mode::Sub(_) | Mode::Nested) {
+                self.state = Err(Error::Parse(syn::parse::Error::new_spanned(
+                    &i.ty,
+                    "`builder(const)` only supports plain and `Option` fields",
+                )));
+
+                return;
             }
 
-            _ => match self.state {
-                Ok(_) => self.state = Err(Error::PhaseMismatch),
-                _ => (),
-            },
+            state.methods.push(method);
         }
 
         visit_field(self, i)
     }
 }
 
+fn extract_method(ident: &Ident, i: &syn::Field) -> Result<Method, syn::parse::Error> {
+    let name = ident.clone();
+
+    if let Some(ty) = extract_vec(&i.ty) {
+        let attrs = extract_field_attrs(&i.attrs, "expected `builder(each = \"...\")`")?;
+        if let Some(list) = attrs.sub_builder {
+            return Err(syn::parse::Error::new_spanned(
+                list,
+                "`builder(sub_builder)` is not supported on `Vec` fields",
+            ));
+        }
+
+        let ty = ty.clone();
+        let mode = Mode::Sub(attrs.each.map(|(each, _)| each));
+
+        return Ok(Method { name, ty, mode });
+    }
+
+    let attrs = extract_field_attrs(&i.attrs, "expected `builder(sub_builder)`")?;
+    if let Some((_, list)) = attrs.each {
+        return Err(syn::parse::Error::new_spanned(
+            list,
+            "`builder(each = \"...\")` can only be used on `Vec` fields",
+        ));
+    }
+
+    if let Some(ty) = extract_option(&i.ty) {
+        if let Some(list) = attrs.sub_builder {
+            return Err(syn::parse::Error::new_spanned(
+                list,
+                "`builder(sub_builder)` is not supported on `Option` fields",
+            ));
+        }
+
+        let ty = ty.clone();
+        let mode = Mode::Optional;
+
+        return Ok(Method { name, ty, mode });
+    }
+
+    let ty = i.ty.clone();
+    let mode = if attrs.sub_builder.is_some() {
+        Mode::Nested
+    } else {
+        Mode::Single
+    };
+
+    Ok(Method { name, ty, mode })
+}
+
 fn extract_option(ty: &Type) -> Option<&Type> {
     let Type::Path(path) = ty else { return None };
-    let Some(segment) = path.path.segments.first() else { return None };
+    let segment = path.path.segments.first()?;
     if segment.ident != "Option" {
         return None;
     };
@@ -95,7 +132,7 @@ fn extract_option(ty: &Type) -> Option<&Type> {
 
 fn extract_vec(ty: &Type) -> Option<&Type> {
     let Type::Path(path) = ty else { return None };
-    let Some(segment) = path.path.segments.first() else { return None };
+    let segment = path.path.segments.first()?;
     if segment.ident != "Vec" {
         return None;
     };
@@ -110,7 +147,17 @@ fn extract_vec(ty: &Type) -> Option<&Type> {
     Some(ty)
 }
 
-fn extract_each_attr(attrs: &[Attribute]) -> Result<Option<String>, syn::parse::Error> {
+/// The `#[builder(...)]` attributes found on a single field, kept alongside
+/// the attribute they came from for error spans.
+#[derive(Default)]
+struct FieldAttrs {
+    each: Option<(String, syn::MetaList)>,
+    sub_builder: Option<syn::MetaList>,
+}
+
+fn extract_field_attrs(attrs: &[Attribute], expected: &str) -> Result<FieldAttrs, syn::parse::Error> {
+    let mut field_attrs = FieldAttrs::default();
+
     for attr in attrs {
         let syn::Meta::List(ref list) = attr.meta else { continue; };
         if !list.path.is_ident("builder") {
@@ -118,19 +165,60 @@ fn extract_each_attr(attrs: &[Attribute]) -> Result<Option<String>, syn::parse::
         }
 
         if let Ok(assign) = list.parse_args::<Assign>() {
-            if assign.ident == "each" {
-                let syn::Lit::Str(s) = assign.value else { continue };
-                return Ok(Some(s.value()));
-            } else {
-                return Err(syn::parse::Error::new_spanned(
-                    list,
-                    "expected `builder(each = \"...\")`",
-                ));
+            match assign.value {
+                syn::Lit::Str(s) if assign.ident == "each" => {
+                    if field_attrs.each.is_some() {
+                        return Err(syn::parse::Error::new_spanned(
+                            list,
+                            "duplicate `builder(each = \"...\")` attribute",
+                        ));
+                    }
+
+                    field_attrs.each = Some((s.value(), list.clone()));
+                    continue;
+                }
+                _ => (),
+            }
+        } else if let Ok(flag) = list.parse_args::<Ident>() {
+            if flag == "sub_builder" {
+                if field_attrs.sub_builder.is_some() {
+                    return Err(syn::parse::Error::new_spanned(
+                        list,
+                        "duplicate `builder(sub_builder)` attribute",
+                    ));
+                }
+
+                field_attrs.sub_builder = Some(list.clone());
+                continue;
             }
         }
+
+        return Err(syn::parse::Error::new_spanned(list, expected));
+    }
+
+    Ok(field_attrs)
+}
+
+fn extract_const_attr(attrs: &[Attribute]) -> Result<bool, syn::parse::Error> {
+    let mut constant = false;
+
+    for attr in attrs {
+        let syn::Meta::List(ref list) = attr.meta else { continue; };
+        if !list.path.is_ident("builder") {
+            continue;
+        }
+
+        if list.parse_args::<syn::Token![const]>().is_err() {
+            return Err(syn::parse::Error::new_spanned(
+                list,
+                "expected `builder(const)`",
+            ));
+        }
+
+        constant = true;
     }
 
-    Ok(None)
+    Ok(constant)
 }
 
 struct Assign {
@@ -0,0 +1,41 @@
+// Every struct deriving Builder also implements the builder_runtime::Buildable
+// trait, and its builder implements builder_runtime::Builder. This lets
+// generic code construct any derived type through its builder without knowing
+// the concrete builder type.
+//
+//     impl builder_runtime::Buildable for Command {
+//         type Builder = CommandBuilder;
+//         ...
+//     }
+//
+//     impl builder_runtime::Builder for CommandBuilder {
+//         type Target = Command;
+//         type Error = builder_runtime::UninitializedField;
+//         ...
+//     }
+
+use builder_runtime::{Buildable, Builder as _};
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    current_dir: Option<String>,
+}
+
+fn build_default<T: Buildable>() -> Result<T, <T::Builder as builder_runtime::Builder>::Error> {
+    T::builder().build()
+}
+
+fn main() {
+    let err = build_default::<Command>().err().unwrap();
+    assert_eq!(err.field(), "executable");
+    assert_eq!(err.to_string(), "`executable` was never set");
+
+    let mut builder = <Command as Buildable>::builder();
+    builder.executable("cargo".to_owned());
+
+    let command = builder_runtime::Builder::build(&builder).unwrap();
+    assert_eq!(command.executable, "cargo");
+    assert_eq!(command.current_dir, None);
+}
@@ -0,0 +1,46 @@
+// Fields whose type also derives Builder can be marked with
+// #[builder(sub_builder)]. Instead of a setter taking the whole value, the
+// outer builder then holds the inner builder and hands out a mutable
+// reference to it.
+//
+//     impl ConfigBuilder {
+//         pub fn server(&mut self) -> &mut ServerConfigBuilder {
+//             ...
+//         }
+//     }
+//
+// The outer build() builds each sub-builder, and a field missing inside of it
+// is reported with its full path, e.g. `server.port`.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct ServerConfig {
+    host: String,
+    port: u16,
+}
+
+#[derive(Builder)]
+pub struct Config {
+    name: String,
+    #[builder(sub_builder)]
+    server: ServerConfig,
+}
+
+fn main() {
+    let mut builder = Config::builder();
+    builder.name("api".to_owned());
+    builder.server().host("localhost".to_owned());
+
+    let err = builder.build().err().unwrap();
+    assert_eq!(err.field(), "port");
+    assert_eq!(err.path(), "server.port");
+    assert_eq!(err.to_string(), "`server.port` was never set");
+
+    builder.server().port(8080);
+
+    let config = builder.build().unwrap();
+    assert_eq!(config.name, "api");
+    assert_eq!(config.server.host, "localhost");
+    assert_eq!(config.server.port, 8080);
+}
@@ -0,0 +1,45 @@
+// Builders used to back forms need to inspect and reset individual fields.
+// For every field generate a `clear_<field>` method that puts it back into its
+// initial state and a `<field>_is_set` method reporting whether it has a value.
+// `missing_fields` lists the required fields which would make build() fail.
+//
+//     impl CommandBuilder {
+//         pub fn clear_executable(&mut self) -> &mut Self { ... }
+//         pub fn executable_is_set(&self) -> bool { ... }
+//         pub fn missing_fields(&self) -> Vec<&'static str> { ... }
+//     }
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+    env: Vec<String>,
+    current_dir: Option<String>,
+}
+
+fn main() {
+    let mut builder = Command::builder();
+    assert!(!builder.executable_is_set());
+    assert!(!builder.args_is_set());
+    assert_eq!(builder.missing_fields(), vec!["executable", "env"]);
+
+    builder
+        .executable("cargo".to_owned())
+        .arg("build".to_owned())
+        .env(vec![])
+        .current_dir("..".to_owned());
+    assert!(builder.executable_is_set());
+    assert!(builder.args_is_set());
+    assert!(builder.current_dir_is_set());
+    assert!(builder.missing_fields().is_empty());
+
+    builder.clear_executable().clear_args().clear_current_dir();
+    assert!(!builder.executable_is_set());
+    assert!(!builder.args_is_set());
+    assert!(!builder.current_dir_is_set());
+    assert_eq!(builder.missing_fields(), vec!["executable"]);
+    assert!(builder.build().is_err());
+}
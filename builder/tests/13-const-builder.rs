@@ -0,0 +1,55 @@
+// With #[builder(const)] on the struct the builder is made usable in const
+// contexts. Setters take and return the builder by value and build() panics
+// at compile time when a required field is missing.
+//
+//     impl LimitsBuilder {
+//         pub const fn max_connections(self, max_connections: u32) -> Self { ... }
+//         pub const fn build(self) -> Limits { ... }
+//     }
+//
+// Only plain and Option fields are supported, and their types must be usable
+// in const contexts (in practice, Copy types).
+
+use derive_builder::Builder;
+
+#[derive(Builder, Debug, PartialEq)]
+#[builder(const)]
+pub struct Limits {
+    max_connections: u32,
+    timeout_ms: u64,
+    retries: Option<u8>,
+}
+
+const LIMITS: Limits = Limits::builder()
+    .max_connections(64)
+    .timeout_ms(30_000)
+    .build();
+
+const BUILDER: LimitsBuilder = Limits::builder().max_connections(8);
+
+fn main() {
+    assert_eq!(LIMITS.max_connections, 64);
+    assert_eq!(LIMITS.timeout_ms, 30_000);
+    assert_eq!(LIMITS.retries, None);
+
+    assert!(BUILDER.max_connections_is_set());
+    assert_eq!(BUILDER.missing_fields(), vec!["timeout_ms"]);
+
+    let err = builder_runtime::Builder::build(&BUILDER).unwrap_err();
+    assert_eq!(err.field(), "timeout_ms");
+
+    let limits = BUILDER
+        .timeout_ms(10)
+        .retries(3)
+        .clear_max_connections()
+        .max_connections(1)
+        .build();
+    assert_eq!(
+        limits,
+        Limits {
+            max_connections: 1,
+            timeout_ms: 10,
+            retries: Some(3),
+        }
+    );
+}
@@ -0,0 +1,14 @@
+// A field may only name one single-element setter. Report the second
+// #[builder(each = "...")] instead of silently picking one of them.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg")]
+    #[builder(each = "argument")]
+    args: Vec<String>,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+// #[builder(each = "...")] only makes sense on Vec fields, since the generated
+// setter pushes one element at a time.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    #[builder(each = "exe")]
+    executable: String,
+}
+
+fn main() {}
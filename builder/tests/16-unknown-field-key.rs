@@ -0,0 +1,17 @@
+// Misspelled keys on fields that are not Vecs are rejected as well, pointing
+// at the offending attribute.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct ServerConfig {
+    port: u16,
+}
+
+#[derive(Builder)]
+pub struct Config {
+    #[builder(subbuilder)]
+    server: ServerConfig,
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+// Sub-builders are always built by the outer build(), so they cannot be used
+// for Option fields which are allowed to stay unset.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct ServerConfig {
+    port: u16,
+}
+
+#[derive(Builder)]
+pub struct Config {
+    #[builder(sub_builder)]
+    server: Option<ServerConfig>,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+// Setters are named after the fields, so tuple structs are rejected.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command(String, Vec<String>);
+
+fn main() {}
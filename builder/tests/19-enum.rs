@@ -0,0 +1,11 @@
+// Builder can only be derived for structs.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub enum Command {
+    Run { executable: String },
+    Exit,
+}
+
+fn main() {}
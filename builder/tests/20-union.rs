@@ -0,0 +1,11 @@
+// Builder can only be derived for structs.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub union Register {
+    raw: u32,
+    bytes: [u8; 4],
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+// The only attribute accepted on the struct itself is #[builder(const)].
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(constant)]
+pub struct Limits {
+    max_connections: u32,
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+// Const builders cannot hold Vec fields or sub-builders, since neither can be
+// filled in a const context.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(const)]
+pub struct Limits {
+    max_connections: u32,
+    #[builder(each = "port")]
+    ports: Vec<u16>,
+}
+
+fn main() {}
@@ -10,4 +10,17 @@ fn tests() {
     t.pass("tests/07-repeated-field.rs");
     t.compile_fail("tests/08-unrecognized-attribute.rs");
     t.pass("tests/09-redefined-prelude-types.rs");
+    t.pass("tests/10-buildable-trait.rs");
+    t.pass("tests/11-sub-builder.rs");
+    t.pass("tests/12-field-introspection.rs");
+    t.pass("tests/13-const-builder.rs");
+    t.compile_fail("tests/14-duplicate-each.rs");
+    t.compile_fail("tests/15-each-on-non-vec.rs");
+    t.compile_fail("tests/16-unknown-field-key.rs");
+    t.compile_fail("tests/17-sub-builder-on-option.rs");
+    t.compile_fail("tests/18-tuple-struct.rs");
+    t.compile_fail("tests/19-enum.rs");
+    t.compile_fail("tests/20-union.rs");
+    t.compile_fail("tests/21-unknown-struct-key.rs");
+    t.compile_fail("tests/22-const-vec-field.rs");
 }